@@ -0,0 +1,292 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+// What a part came up with. Numbers compare equal whichever variant they're in.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i128),
+    UInt(u128),
+    Text(String),
+    Unsolved,
+}
+impl Answer {
+    // The answer as a number, if it is one
+    fn number(&self) -> Option<i128> {
+        match self {
+            Answer::Int(value) => Some(*value),
+            Answer::UInt(value) => i128::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn check(&self, expected: Option<&Answer>) -> Verdict {
+        match (self, expected) {
+            (Answer::Unsolved, _) => Verdict::Unsolved,
+            (_, None) => Verdict::Unknown,
+            (answer, Some(expected)) if answer == expected => Verdict::Correct,
+            (_, Some(expected)) => Verdict::Wrong(expected.clone()),
+        }
+    }
+}
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::UInt(a), Answer::UInt(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Unsolved, Answer::Unsolved) => true,
+            _ => self.number().is_some() && self.number() == other.number(),
+        }
+    }
+}
+impl Eq for Answer {}
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Padded, so that answers line up in tables
+        match self {
+            Answer::Int(value) => f.pad(&value.to_string()),
+            Answer::UInt(value) => f.pad(&value.to_string()),
+            Answer::Text(text) => f.pad(text),
+            Answer::Unsolved => f.pad("todo!"),
+        }
+    }
+}
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || s == "todo!" {
+            Ok(Answer::Unsolved)
+        } else if let Ok(value) = s.parse() {
+            Ok(Answer::UInt(value))
+        } else if let Ok(value) = s.parse() {
+            Ok(Answer::Int(value))
+        } else {
+            Ok(Answer::Text(s.to_string()))
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident: $($number:ty),*) => {
+        $(impl From<$number> for Answer {
+            fn from(value: $number) -> Self {
+                Answer::$variant(value.into())
+            }
+        })*
+    };
+}
+answer_from!(Int: i32, i64, i128);
+answer_from!(UInt: u32, u64, u128);
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Int(value as i128)
+    }
+}
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::UInt(value as u128)
+    }
+}
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+// How an answer measures up to the one known to be right
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Answer),
+    Unsolved,
+    // There's no known answer to check against
+    Unknown,
+}
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.pad("ok"),
+            Verdict::Wrong(expected) => f.pad(&format!("expected {}", expected)),
+            Verdict::Unsolved => f.pad("unsolved"),
+            Verdict::Unknown => f.pad("?"),
+        }
+    }
+}
+
+// The answer written into a saved puzzle page, as the site shows it once the
+// part is solved: "Your puzzle answer was <code>42</code>."
+pub fn stored_answer(page: &str) -> Option<Answer> {
+    let (_, rest) = page.split_once("Your puzzle answer was <code>")?;
+    let (answer, _) = rest.split_once("</code>")?;
+    Some(answer.parse().unwrap())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Syntax { line: usize, message: String },
+    Invalid(String),
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Syntax { line, message } => write!(f, "Line {}: {}", line, message),
+            ParseError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+impl std::error::Error for ParseError {}
+
+// A day's puzzle. The input is parsed once and both parts work from that.
+pub trait Solution {
+    const DAY: u8;
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(parsed: &Self::Parsed) -> Answer;
+    fn part2(parsed: &Self::Parsed) -> Answer;
+}
+
+// The answers from one go at an input, and how long each step took
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub parse: Duration,
+    pub part1: (Answer, Duration),
+    pub part2: (Answer, Duration),
+}
+
+pub fn run<S: Solution>(input: &str) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer1 = S::part1(&parsed);
+    let part1 = (answer1, start.elapsed());
+
+    let start = Instant::now();
+    let answer2 = S::part2(&parsed);
+    let part2 = (answer2, start.elapsed());
+
+    Ok(Run { parse, part1, part2 })
+}
+
+// A Solution with its types erased, so that days can be listed together.
+// Made by register!.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    // The day's crate, where input.txt is
+    pub dir: &'static str,
+    pub run: fn(&str) -> Result<Run, ParseError>,
+}
+impl Day {
+    pub const fn new<S: Solution>(dir: &'static str) -> Self {
+        Self {
+            number: S::DAY,
+            dir,
+            run: run::<S>,
+        }
+    }
+
+    pub fn input(&self) -> std::io::Result<String> {
+        std::fs::read_to_string(Path::new(self.dir).join("input.txt"))
+    }
+
+    // The known answer to a part, from the saved partN.txt if it has one
+    pub fn expected(&self, part: u8) -> Option<Answer> {
+        let page = std::fs::read_to_string(Path::new(self.dir).join(format!("part{}.txt", part))).ok()?;
+        stored_answer(&page)
+    }
+}
+
+// Declares the Day for a Solution as DAY in the crate it's used in
+#[macro_export]
+macro_rules! register {
+    ($solution:ty) => {
+        pub const DAY: $crate::Day = $crate::Day::new::<$solution>(env!("CARGO_MANIFEST_DIR"));
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Words;
+    impl Solution for Words {
+        const DAY: u8 = 0;
+        type Parsed = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            if input.is_empty() {
+                return Err(ParseError::Invalid("No words".to_string()));
+            }
+            Ok(input.split_whitespace().map(str::to_string).collect())
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Answer {
+            parsed.len().into()
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Answer {
+            parsed.iter().map(String::len).max().unwrap().into()
+        }
+    }
+
+    register!(Words);
+
+    #[test]
+    fn test_run() {
+        let run = (DAY.run)("one two three").unwrap();
+        assert_eq!(run.part1.0, Answer::UInt(3));
+        assert_eq!(run.part2.0, Answer::UInt(5));
+        assert_eq!(DAY.number, 0);
+        assert_eq!(DAY.dir, env!("CARGO_MANIFEST_DIR"));
+        assert_eq!((DAY.run)("").unwrap_err().to_string(), "No words");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = ParseError::Syntax { line: 3, message: "Expected a number".to_string() };
+        assert_eq!(error.to_string(), "Line 3: Expected a number");
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!("42".parse::<Answer>().unwrap(), Answer::UInt(42));
+        assert_eq!("-42".parse::<Answer>().unwrap(), Answer::Int(-42));
+        assert_eq!("todo!".parse::<Answer>().unwrap(), Answer::Unsolved);
+        assert_eq!("LRRL".parse::<Answer>().unwrap(), Answer::Text("LRRL".to_string()));
+
+        assert_eq!(Answer::from(42i64), Answer::UInt(42));
+        assert_ne!(Answer::from(-42i64), Answer::UInt(42));
+        assert_ne!(Answer::UInt(u128::MAX), Answer::Int(-1));
+        assert_ne!(Answer::Text("42".to_string()), Answer::UInt(42));
+        assert_eq!(Answer::Int(-42).to_string(), "-42");
+        assert_eq!(Answer::Unsolved.to_string(), "todo!");
+        assert_eq!(format!("{:>4}", Answer::UInt(42)), "  42");
+    }
+
+    #[test]
+    fn test_check() {
+        let page = "<p>What is it?</p><p>Your puzzle answer was <code>405</code>.</p>";
+        let expected = stored_answer(page);
+        assert_eq!(expected, Some(Answer::UInt(405)));
+        assert_eq!(stored_answer("<p>What is it?</p>"), None);
+
+        assert_eq!(Answer::Int(405).check(expected.as_ref()), Verdict::Correct);
+        assert_eq!(Answer::Int(406).check(expected.as_ref()), Verdict::Wrong(Answer::UInt(405)));
+        assert_eq!(Answer::Unsolved.check(expected.as_ref()), Verdict::Unsolved);
+        assert_eq!(Answer::Int(405).check(None), Verdict::Unknown);
+        assert_eq!(Answer::Unsolved.check(None), Verdict::Unsolved);
+    }
+}
@@ -1,11 +1,14 @@
+use aoc::{Answer, Solution};
+use day_00::Day00;
+
 fn main() {
     let input = include_str!("../../input.txt");
     let output = part1(input);
     dbg!(output);
 }
 
-fn part1(input: &str) -> String {
-    return "todo!".to_string();
+fn part1(input: &str) -> Answer {
+    Day00::part1(&Day00::parse(input).unwrap())
 }
 
 #[cfg(test)]
@@ -17,6 +20,6 @@ mod tests {
     #[test]
     fn it_works1() {
         let result = part1(INPUT);
-        assert_eq!(result, "todo!".to_string());
+        assert_eq!(result, Answer::Unsolved);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,21 @@
+use aoc::{Answer, ParseError, Solution};
+
+pub struct Day00;
+impl Solution for Day00 {
+    const DAY: u8 = 0;
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(_lines: &Self::Parsed) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(_lines: &Self::Parsed) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+aoc::register!(Day00);
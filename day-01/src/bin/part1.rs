@@ -1,43 +1,14 @@
+use aoc::{Answer, Solution};
+use day_01::Day01;
+
 fn main() {
     let input = include_str!("../../input.txt");
     let output = part1(input);
     dbg!(output);
 }
 
-fn part1(input: &str) -> String {
-    let mut sum = 0;
-
-    for line in input.lines() {
-        let mut first_digit = String::new();
-        let mut last_digit = String::new();
-
-        // Find the first number in the line
-        for c in line.chars() {
-            if c.is_digit(10) {
-                first_digit = c.to_string();
-                break;
-            }
-        }
-
-        // Find the last number in the line
-        for c in line.chars().rev() {
-            if c.is_digit(10) {
-                last_digit = c.to_string();
-                break;
-            }
-        }
-        
-        // Combine digits to a number and convert to integer
-        let whole_number = first_digit + &last_digit;
-
-        let number = whole_number.parse::<i32>().unwrap();
-
-        println!("number: {}", number);
-
-        sum += number;
-    }
-
-    sum.to_string()
+fn part1(input: &str) -> Answer {
+    Day01::part1(&Day01::parse(input).unwrap())
 }
 
 #[cfg(test)]
@@ -51,6 +22,6 @@ pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet",
         );
-        assert_eq!(result, "142".to_string());
+        assert_eq!(result, Answer::UInt(142));
     }
 }
\ No newline at end of file
@@ -0,0 +1,50 @@
+use aoc::{Answer, ParseError, Solution};
+
+const DIGITS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+// The digit spelled out at the start of the line from i, if there is one
+fn convert_to_number(line: &str, i: usize) -> Option<u32> {
+    DIGITS
+        .iter()
+        .position(|digit| line[i..].starts_with(digit))
+        .map(|position| position as u32 + 1)
+}
+
+// The first and last digit of the line as a two digit number, where digits
+// can be spelled out if words is set
+fn calibration_value(line: &str, words: bool) -> u32 {
+    let digit_at = |i: usize| -> Option<u32> {
+        let c = line[i..].chars().next().unwrap();
+        match c.to_digit(10) {
+            Some(digit) => Some(digit),
+            None if words => convert_to_number(line, i),
+            None => None,
+        }
+    };
+
+    let first_digit = (0..line.len()).find_map(digit_at).unwrap_or(0);
+    let last_digit = (0..line.len()).rev().find_map(digit_at).unwrap_or(0);
+    first_digit * 10 + last_digit
+}
+
+pub struct Day01;
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(lines: &Self::Parsed) -> Answer {
+        let sum: u32 = lines.iter().map(|line| calibration_value(line, false)).sum();
+        sum.into()
+    }
+
+    fn part2(lines: &Self::Parsed) -> Answer {
+        let sum: u32 = lines.iter().map(|line| calibration_value(line, true)).sum();
+        sum.into()
+    }
+}
+
+aoc::register!(Day01);
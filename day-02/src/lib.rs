@@ -0,0 +1,79 @@
+use aoc::{Answer, ParseError, Solution};
+
+// The most cubes of each color shown in any round of a game
+#[derive(Debug, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub max_red: u32,
+    pub max_green: u32,
+    pub max_blue: u32,
+}
+
+fn parse_game(line: &str) -> Option<Game> {
+    let (game_part, rounds_part) = line.split_once(": ")?;
+    let id = game_part.strip_prefix("Game ")?.parse().ok()?;
+    let mut game = Game { id, max_red: 0, max_green: 0, max_blue: 0 };
+
+    for round in rounds_part.split("; ") {
+        for cube in round.split(", ") {
+            let (count, color) = cube.split_once(' ')?;
+            let count: u32 = count.parse().ok()?;
+            let max = match color {
+                "red" => &mut game.max_red,
+                "green" => &mut game.max_green,
+                "blue" => &mut game.max_blue,
+                _ => return None,
+            };
+            *max = (*max).max(count);
+        }
+    }
+
+    Some(game)
+}
+
+pub struct Day02;
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Parsed = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                parse_game(line).ok_or_else(|| ParseError::Syntax { line: i + 1, message: format!("Not a game: {}", line) })
+            })
+            .collect()
+    }
+
+    fn part1(games: &Self::Parsed) -> Answer {
+        let sum: u32 = games
+            .iter()
+            .filter(|game| game.max_red <= 12 && game.max_green <= 13 && game.max_blue <= 14)
+            .map(|game| game.id)
+            .sum();
+        sum.into()
+    }
+
+    fn part2(games: &Self::Parsed) -> Answer {
+        let sum: u32 = games.iter().map(|game| game.max_red * game.max_green * game.max_blue).sum();
+        sum.into()
+    }
+}
+
+aoc::register!(Day02);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let games = Day02::parse("Game 7: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+        assert_eq!(games, vec![Game { id: 7, max_red: 4, max_green: 2, max_blue: 6 }]);
+        assert_eq!(
+            Day02::parse("Game 1: 3 blue\nGame 2: 3 purple"),
+            Err(ParseError::Syntax { line: 2, message: "Not a game: Game 2: 3 purple".to_string() })
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use aoc::{Answer, ParseError, Solution};
+
+// A part number spanning start_x to end_x on line y
+#[derive(Debug, PartialEq, Eq)]
+pub struct Number {
+    pub start_x: i32,
+    pub end_x: i32,
+    pub y: i32,
+    pub value: u32,
+}
+impl Number {
+    // Whether the position is directly to the left, right, above, below, or
+    // diagonally next to the number
+    fn is_adjacent(&self, x: i32, y: i32) -> bool {
+        (self.start_x - 1..=self.end_x + 1).contains(&x) && (self.y - 1..=self.y + 1).contains(&y)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Schematic {
+    // Everything that's neither a digit nor a dot
+    pub symbols: Vec<(i32, i32, char)>,
+    pub numbers: Vec<Number>,
+}
+
+pub struct Day03;
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Parsed = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut symbols = Vec::new();
+        let mut numbers = Vec::new();
+
+        for (y, line) in input.lines().enumerate() {
+            let y = y as i32;
+            let mut number: Option<Number> = None;
+            for (x, c) in line.chars().enumerate() {
+                let x = x as i32;
+                match c.to_digit(10) {
+                    Some(digit) => {
+                        let number = number.get_or_insert(Number { start_x: x, end_x: x, y, value: 0 });
+                        number.end_x = x;
+                        number.value = number.value * 10 + digit;
+                    },
+                    None => {
+                        numbers.extend(number.take());
+                        if c != '.' {
+                            symbols.push((x, y, c));
+                        }
+                    },
+                }
+            }
+            numbers.extend(number);
+        }
+
+        Ok(Schematic { symbols, numbers })
+    }
+
+    fn part1(schematic: &Self::Parsed) -> Answer {
+        let sum: u32 = schematic
+            .numbers
+            .iter()
+            .filter(|number| schematic.symbols.iter().any(|&(x, y, _)| number.is_adjacent(x, y)))
+            .map(|number| number.value)
+            .sum();
+        sum.into()
+    }
+
+    fn part2(schematic: &Self::Parsed) -> Answer {
+        // Gears are stars next to exactly two numbers
+        let mut sum = 0;
+        for &(x, y, c) in &schematic.symbols {
+            if c != '*' {
+                continue;
+            }
+            let adjacent: Vec<u32> = schematic
+                .numbers
+                .iter()
+                .filter(|number| number.is_adjacent(x, y))
+                .map(|number| number.value)
+                .collect();
+            if adjacent.len() == 2 {
+                sum += adjacent[0] * adjacent[1];
+            }
+        }
+        sum.into()
+    }
+}
+
+aoc::register!(Day03);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let schematic = Day03::parse("467..114\n...*....\n..35..63").unwrap();
+        assert_eq!(schematic.symbols, vec![(3, 1, '*')]);
+        assert_eq!(
+            schematic.numbers,
+            vec![
+                Number { start_x: 0, end_x: 2, y: 0, value: 467 },
+                Number { start_x: 5, end_x: 7, y: 0, value: 114 },
+                Number { start_x: 2, end_x: 3, y: 2, value: 35 },
+                Number { start_x: 6, end_x: 7, y: 2, value: 63 },
+            ]
+        );
+    }
+}
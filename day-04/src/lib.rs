@@ -0,0 +1,135 @@
+use aoc::{Answer, ParseError, Solution};
+
+// The cards won by each card, by number
+pub fn generate_card_result_list(cards: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut result_list = Vec::new();
+    for (i, &(_num, score)) in cards.iter().enumerate() {
+        let result = cards[i + 1..(i + score + 1).min(cards.len())].iter().map(|card| card.0).collect();
+        result_list.push(result);
+    }
+    result_list
+}
+
+// The cards won by a pile of cards
+pub fn find_new_cards(cards: &[usize], result_list: &[Vec<usize>]) -> Vec<usize> {
+    cards.iter().flat_map(|card| result_list[*card - 1].iter().copied()).collect()
+}
+
+pub struct Day04;
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    // The number of every card and how many of its own numbers are winning
+    // numbers
+    type Parsed = Vec<(usize, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let error = || ParseError::Syntax { line: i + 1, message: format!("Not a card: {}", line) };
+                let (card_part, rounds_part) = line.split_once(": ").ok_or_else(error)?;
+                let num = card_part.split_whitespace().nth(1).and_then(|num| num.parse().ok()).ok_or_else(error)?;
+                let (winning_numbers, own_numbers) = rounds_part.split_once(" | ").ok_or_else(error)?;
+                let own_numbers: Vec<&str> = own_numbers.split_whitespace().collect();
+                let score = winning_numbers.split_whitespace().filter(|number| own_numbers.contains(number)).count();
+                Ok((num, score))
+            })
+            .collect()
+    }
+
+    fn part1(cards: &Self::Parsed) -> Answer {
+        let sum: u32 = cards.iter().filter(|card| card.1 > 0).map(|card| 1 << (card.1 - 1)).sum();
+        sum.into()
+    }
+
+    fn part2(cards: &Self::Parsed) -> Answer {
+        let rl = generate_card_result_list(cards);
+
+        // Keep trading the cards won last time in until none are won
+        let mut all_cards = 0;
+        let mut old_cards: Vec<usize> = cards.iter().map(|card| card.0).collect();
+        while !old_cards.is_empty() {
+            all_cards += old_cards.len();
+            old_cards = find_new_cards(&old_cards, &rl);
+        }
+        all_cards.into()
+    }
+}
+
+aoc::register!(Day04);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn get_original_cards_test() {
+        println!("Original cards: ");
+        let cards = Day04::parse(INPUT).unwrap();
+        for (i, card) in cards.iter().enumerate() {
+            println!("Card {} -> {:?}", i, card);
+        }
+        assert_eq!(cards.len(), 6);
+        assert_eq!(cards[0], (1, 4));
+        assert_eq!(cards[1], (2, 2));
+        assert_eq!(cards[2], (3, 2));
+        assert_eq!(cards[3], (4, 1));
+        assert_eq!(cards[4], (5, 0));
+        assert_eq!(cards[5], (6, 0));
+    }
+
+    #[test]
+    fn result_list_test() {
+        let cards = Day04::parse(INPUT).unwrap();
+
+        println!("Result list: ");
+        let rl = generate_card_result_list(&cards);
+        for (i, card) in rl.iter().enumerate() {
+            println!("Card {} -> {:?}", i, card);
+        }
+        assert_eq!(rl.len(), 6);
+        assert_eq!(rl[0], vec![2, 3, 4, 5]);
+        assert_eq!(rl[1], vec![3, 4]);
+        assert_eq!(rl[2], vec![4, 5]);
+        assert_eq!(rl[3], vec![5]);
+        assert_eq!(rl[4], vec![]);
+        assert_eq!(rl[5], vec![]);
+    }
+
+    #[test]
+    fn find_new_cards_test() {
+        let cards = Day04::parse(INPUT).unwrap();
+        let rl = generate_card_result_list(&cards);
+
+        let mut old_cards = Vec::new();
+        for card in cards.iter() {
+            let (num, _) = card;
+            old_cards.push(*num);
+        }
+
+        println!("New cards: ");
+        let new_cards = find_new_cards(&old_cards, &rl);
+        for (i, card) in new_cards.iter().enumerate() {
+            println!("Card {} -> {:?}", i, card);
+        }
+        assert_eq!(new_cards.len(), 9);
+        assert_eq!(new_cards[0], 2);
+        assert_eq!(new_cards[1], 3);
+        assert_eq!(new_cards[2], 4);
+        assert_eq!(new_cards[3], 5);
+        assert_eq!(new_cards[4], 3);
+        assert_eq!(new_cards[5], 4);
+        assert_eq!(new_cards[6], 4);
+        assert_eq!(new_cards[7], 5);
+        assert_eq!(new_cards[8], 5);
+    }
+}
@@ -0,0 +1,405 @@
+use aoc::{Answer, ParseError, Solution};
+
+// The maps in the order they come in, each one taking the previous one's
+// destinations as its sources
+const MAP_NAMES: [&str; 7] = [
+    "seed-to-soil map:",
+    "soil-to-fertilizer map:",
+    "fertilizer-to-water map:",
+    "water-to-light map:",
+    "light-to-temperature map:",
+    "temperature-to-humidity map:",
+    "humidity-to-location map:",
+];
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Map {
+    pub destination: usize,
+    pub source: usize,
+    pub range: usize,
+}
+
+// Everything from start up to but not including end
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct ResourceRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Almanac {
+    pub seeds: Vec<usize>,
+    pub maps: Vec<Vec<Map>>,
+}
+
+fn syntax(line: usize, message: &str) -> ParseError {
+    ParseError::Syntax { line: line + 1, message: message.to_string() }
+}
+
+pub fn create_seed_list(input: &str) -> Result<Vec<usize>, ParseError> {
+    let line = input.lines().next().unwrap_or("");
+    let seeds_str = line.strip_prefix("seeds: ").ok_or_else(|| syntax(0, "Expected seeds"))?;
+    seeds_str
+        .split_whitespace()
+        .map(|seed| seed.parse::<usize>().map_err(|_| syntax(0, &format!("Invalid seed: {}", seed))))
+        .collect()
+}
+
+// Part 2 reads the seeds as pairs of start and length
+pub fn create_seed_ranges(seeds: &[usize]) -> Vec<ResourceRange> {
+    seeds
+        .chunks_exact(2)
+        .map(|pair| ResourceRange { start: pair[0], end: pair[0] + pair[1] })
+        .collect()
+}
+
+// The maps in MAP_NAMES order, and the highest number any of them covers
+pub fn create_map_lists(lines: &[&str]) -> Result<(usize, Vec<Vec<Map>>), ParseError> {
+    let mut maps: Vec<Vec<Map>> = (0..MAP_NAMES.len()).map(|_| Vec::new()).collect();
+    let mut highest_number: usize = 0;
+    let mut currentmap: Option<usize> = None;
+
+    // Skip seeds and empty line
+    for (i, line) in lines.iter().enumerate().skip(2) {
+        match currentmap {
+            None => match MAP_NAMES.iter().position(|name| name == line) {
+                Some(map) => currentmap = Some(map),
+                None => return Err(syntax(i, &format!("Unknown map: {}", line))),
+            },
+            Some(_) if line.is_empty() => currentmap = None,
+            Some(map) => {
+                let parts = line
+                    .split(' ')
+                    .map(|part| part.parse::<usize>())
+                    .collect::<Result<Vec<usize>, _>>()
+                    .map_err(|_| syntax(i, "Expected numbers"))?;
+                let &[destination, source, range] = parts.as_slice() else {
+                    return Err(syntax(i, "Expected destination, source and range"));
+                };
+                maps[map].push(Map { destination, source, range });
+
+                highest_number = highest_number.max(destination + range - 1).max(source + range - 1);
+            },
+        }
+    }
+
+    Ok((highest_number, maps))
+}
+
+pub fn map_destination_to_source(destination: usize, map: &[Map]) -> usize {
+    for m in map {
+        if destination >= m.destination && destination < m.destination + m.range {
+            return m.source + (destination - m.destination);
+        }
+    }
+
+    // If target doesn't exist in the map, it's the same as the source.
+    destination
+}
+
+pub fn map_source_to_destination(source: usize, map: &[Map]) -> usize {
+    for m in map {
+        if source >= m.source && source < m.source + m.range {
+            return m.destination + (source - m.source);
+        }
+    }
+
+    // If target doesn't exist in the map, it's the same as the source.
+    source
+}
+
+// Takes a list of ranges and a map, and returns the ranges they map to, taking
+// care to split ranges that only partly overlap a map entry. The parts that
+// fall outside an entry are tried again against the rest of the map.
+pub fn map_sources_to_destinations(sources: &[ResourceRange], map: &[Map]) -> Vec<ResourceRange> {
+    let mut destinations: Vec<ResourceRange> = Vec::new();
+    let mut local_sources = sources.to_vec();
+
+    let mut ctr: usize = 0;
+    while ctr < local_sources.len() {
+        let source = local_sources[ctr];
+        ctr += 1;
+
+        let mut added: bool = false;
+
+        for map in map {
+            let map_end = map.source + map.range;
+
+            // If the source range is completely before or after the map range,
+            // skip it.
+            if source.end < map.source || source.start >= map_end {
+                continue;
+            }
+
+            // If the source range is completely within the map range, add the
+            // destination range to the list.
+            if source.start >= map.source && source.end <= map_end {
+                destinations.push(ResourceRange {
+                    start: map.destination + (source.start - map.source),
+                    end: map.destination + (source.end - map.source),
+                });
+                added = true;
+                break;
+            }
+
+            // If the source range is partially before and partially after the
+            // map range, add the destination range to the list, and add the
+            // remaining source ranges to the list.
+            if source.start < map.source && source.end > map_end {
+                destinations.push(ResourceRange { start: map.destination, end: map.destination + map.range });
+                local_sources.push(ResourceRange { start: source.start, end: map.source });
+                local_sources.push(ResourceRange { start: map_end, end: source.end });
+                added = true;
+                break;
+            }
+
+            // If the source range is partially before and partially within the
+            // map range, add the destination range to the list, and add the
+            // remaining source range to the list.
+            if source.start < map.source && source.end > map.source {
+                destinations.push(ResourceRange {
+                    start: map.destination,
+                    end: map.destination + (source.end - map.source),
+                });
+                local_sources.push(ResourceRange { start: source.start, end: map.source });
+                added = true;
+                break;
+            }
+
+            // If the source range is partially within and partially after the
+            // map range, add the destination range to the list, and add the
+            // remaining source range to the list.
+            if source.start >= map.source && source.end > map_end {
+                destinations.push(ResourceRange {
+                    start: map.destination + (source.start - map.source),
+                    end: map.destination + map.range,
+                });
+                local_sources.push(ResourceRange { start: map_end, end: source.end });
+                added = true;
+                break;
+            }
+        }
+
+        if !added {
+            destinations.push(source);
+        }
+    }
+
+    destinations
+}
+
+pub struct Day05;
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Parsed = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let seeds = create_seed_list(input)?;
+        let lines = input.lines().collect::<Vec<&str>>();
+        let (_highest_number, maps) = create_map_lists(&lines)?;
+        Ok(Almanac { seeds, maps })
+    }
+
+    fn part1(almanac: &Self::Parsed) -> Answer {
+        // Go from seed all the way to location
+        let best_location = almanac
+            .seeds
+            .iter()
+            .map(|&seed| almanac.maps.iter().fold(seed, |source, map| map_source_to_destination(source, map)))
+            .min()
+            .unwrap();
+
+        best_location.into()
+    }
+
+    fn part2(almanac: &Self::Parsed) -> Answer {
+        let best_location = create_seed_ranges(&almanac.seeds)
+            .into_iter()
+            .flat_map(|seed_range| {
+                almanac
+                    .maps
+                    .iter()
+                    .fold(vec![seed_range], |sources, map| map_sources_to_destinations(&sources, map))
+            })
+            .map(|location_range| location_range.start)
+            .min()
+            .unwrap();
+
+        best_location.into()
+    }
+}
+
+aoc::register!(Day05);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    fn map(destination: usize, source: usize, range: usize) -> Map {
+        Map { destination, source, range }
+    }
+
+    #[test]
+    fn create_seed_list_test() {
+        let result = create_seed_list(INPUT).unwrap();
+        assert_eq!(result, vec![79, 14, 55, 13]);
+    }
+
+    #[test]
+    fn create_seed_ranges_test() {
+        let result = create_seed_ranges(&create_seed_list(INPUT).unwrap());
+        assert_eq!(result, vec![ResourceRange { start: 79, end: 93 }, ResourceRange { start: 55, end: 68 }]);
+    }
+
+    #[test]
+    fn map_destination_to_source_test() {
+        let map = vec![map(50, 98, 2), map(52, 50, 48)];
+
+        for (d, s) in [
+            (0, 0),
+            (1, 1),
+            // ...
+            (48, 48),
+            (49, 49),
+            (52, 50),
+            (53, 51),
+            // ...
+            (98, 96),
+            (99, 97),
+            (50, 98),
+            (51, 99),
+        ] {
+            assert_eq!(map_destination_to_source(d, &map), s);
+            assert_eq!(map_source_to_destination(s, &map), d);
+        }
+    }
+
+    #[test]
+    fn create_map_lists_test() {
+        let lines = INPUT.lines().collect::<Vec<&str>>();
+
+        let (high, result) = create_map_lists(&lines).unwrap();
+        assert_eq!(high, 99);
+        assert_eq!(result.len(), 7);
+        assert_eq!(result[0], vec![map(50, 98, 2), map(52, 50, 48)]);
+        assert_eq!(result[1], vec![map(0, 15, 37), map(37, 52, 2), map(39, 0, 15)]);
+        assert_eq!(result[2], vec![map(49, 53, 8), map(0, 11, 42), map(42, 0, 7), map(57, 7, 4)]);
+        assert_eq!(result[3], vec![map(88, 18, 7), map(18, 25, 70)]);
+        assert_eq!(result[4], vec![map(45, 77, 23), map(81, 45, 19), map(68, 64, 13)]);
+        assert_eq!(result[5], vec![map(0, 69, 1), map(1, 0, 69)]);
+        assert_eq!(result[6], vec![map(60, 56, 37), map(56, 93, 4)]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let lines = "seeds: 1 2\n\nseed-to-dirt map:\n1 2 3".lines().collect::<Vec<&str>>();
+        assert_eq!(
+            create_map_lists(&lines).unwrap_err(),
+            ParseError::Syntax { line: 3, message: "Unknown map: seed-to-dirt map:".to_string() }
+        );
+        assert!(Day05::parse("seeds: 1 x").is_err());
+    }
+
+    fn map_ranges(start: usize, end: usize) -> Vec<ResourceRange> {
+        map_sources_to_destinations(&[ResourceRange { start, end }], &[map(200, 100, 10)])
+    }
+
+    fn ranges(ranges: &[(usize, usize)]) -> Vec<ResourceRange> {
+        ranges.iter().map(|&(start, end)| ResourceRange { start, end }).collect()
+    }
+
+    #[test]
+    fn before() {
+        assert_eq!(map_ranges(90, 95), ranges(&[(90, 95)]));
+    }
+
+    #[test]
+    fn before_tangent() {
+        assert_eq!(map_ranges(90, 100), ranges(&[(90, 100)]));
+    }
+
+    #[test]
+    fn after() {
+        assert_eq!(map_ranges(120, 125), ranges(&[(120, 125)]));
+    }
+
+    #[test]
+    fn after_tangent() {
+        assert_eq!(map_ranges(110, 120), ranges(&[(110, 120)]));
+    }
+
+    #[test]
+    fn within() {
+        assert_eq!(map_ranges(101, 109), ranges(&[(201, 209)]));
+    }
+
+    #[test]
+    fn within_tangent_start() {
+        assert_eq!(map_ranges(100, 109), ranges(&[(200, 209)]));
+    }
+
+    #[test]
+    fn within_tangent_end() {
+        assert_eq!(map_ranges(101, 110), ranges(&[(201, 210)]));
+    }
+
+    #[test]
+    fn outside() {
+        assert_eq!(map_ranges(90, 120), ranges(&[(200, 210), (90, 100), (110, 120)]));
+    }
+
+    #[test]
+    fn outside_before() {
+        assert_eq!(map_ranges(90, 105), ranges(&[(200, 205), (90, 100)]));
+    }
+
+    #[test]
+    fn outside_before_tangent() {
+        assert_eq!(map_ranges(90, 110), ranges(&[(200, 210), (90, 100)]));
+    }
+
+    #[test]
+    fn outside_after() {
+        assert_eq!(map_ranges(105, 115), ranges(&[(205, 210), (110, 115)]));
+    }
+
+    #[test]
+    fn outside_after_tangent() {
+        assert_eq!(map_ranges(100, 115), ranges(&[(200, 210), (110, 115)]));
+    }
+}
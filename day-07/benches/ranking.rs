@@ -0,0 +1,24 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use day_07::{synthetic_input, Rules};
+
+// Ranking a million hands, parsing included, is meant to take under a second
+// with either rules
+fn ranking(c: &mut Criterion) {
+    let input = synthetic_input(1_000_000);
+
+    let mut group = c.benchmark_group("day-07 1M hands");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1_000_000));
+    group.bench_function("standard", |b| {
+        let rules = Rules::standard();
+        b.iter(|| rules.total_winnings(black_box(&input)).unwrap())
+    });
+    group.bench_function("jokers", |b| {
+        let rules = Rules::jokers();
+        b.iter(|| rules.total_winnings(black_box(&input)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, ranking);
+criterion_main!(benches);
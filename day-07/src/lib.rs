@@ -0,0 +1,444 @@
+use std::fmt;
+
+use aoc::{Answer, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandType {
+    HighCard = 0,
+    OnePair = 1,
+    TwoPairs = 2,
+    ThreeOfAKind = 3,
+    FullHouse = 4,
+    FourOfAKind = 5,
+    FiveOfAKind = 6,
+}
+impl HandType {
+    const ALL: [HandType; 7] = [
+        HandType::HighCard,
+        HandType::OnePair,
+        HandType::TwoPairs,
+        HandType::ThreeOfAKind,
+        HandType::FullHouse,
+        HandType::FourOfAKind,
+        HandType::FiveOfAKind,
+    ];
+
+    // Counts are the number of each distinct card, sorted high to low
+    pub fn from_counts(counts: &[u32]) -> Self {
+        match counts {
+            [5, ..] => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2, ..] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPairs,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+// Hands are always five cards, each card value is packed into four bits
+// below the hand type so that a single integer compare ranks two hands.
+pub const HAND_SIZE: usize = 5;
+const CARD_BITS: u32 = 4;
+const TYPE_SHIFT: u32 = CARD_BITS * HAND_SIZE as u32;
+const NO_CARD: u8 = u8::MAX;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardError {
+    TooManyCards(usize),
+    UnknownWildcard(char),
+    UnknownCard(char),
+    WrongHandSize(String),
+}
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardError::TooManyCards(num_cards) => {
+                write!(f, "{} different cards, at most {} are supported", num_cards, 1 << CARD_BITS)
+            },
+            CardError::UnknownWildcard(card) => write!(f, "Wildcard {} is not part of the card order", card),
+            CardError::UnknownCard(card) => write!(f, "Unknown card {}", card),
+            CardError::WrongHandSize(cards) => write!(f, "Hand {} does not have {} cards", cards, HAND_SIZE),
+        }
+    }
+}
+impl std::error::Error for CardError {}
+
+#[derive(Debug, Clone)]
+pub struct Rules {
+    // Card value indexed by the card's ASCII byte, lowest card has value 0
+    values: [u8; 256],
+    // Cards that count as whichever card makes the best hand
+    wildcards: [bool; 256],
+    num_cards: usize,
+}
+impl Rules {
+    pub fn new(order: &str, wildcards: &str) -> Result<Self, CardError> {
+        if order.chars().count() > 1 << CARD_BITS {
+            return Err(CardError::TooManyCards(order.chars().count()));
+        }
+        if let Some(card) = order.chars().find(|card| !card.is_ascii()) {
+            return Err(CardError::UnknownCard(card));
+        }
+
+        let mut values = [NO_CARD; 256];
+        for (value, card) in order.bytes().enumerate() {
+            values[card as usize] = value as u8;
+        }
+
+        let mut is_wildcard = [false; 256];
+        for card in wildcards.chars() {
+            if !card.is_ascii() || values[card as usize] == NO_CARD {
+                return Err(CardError::UnknownWildcard(card));
+            }
+            is_wildcard[card as usize] = true;
+        }
+
+        Ok(Self {
+            values,
+            wildcards: is_wildcard,
+            num_cards: order.len(),
+        })
+    }
+
+    pub fn standard() -> Self {
+        Self::new("23456789TJQKA", "").unwrap()
+    }
+
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA", "J").unwrap()
+    }
+
+    pub fn card_value(&self, card: char) -> Result<u32, CardError> {
+        let value = if card.is_ascii() { self.values[card as usize] } else { NO_CARD };
+        if value == NO_CARD {
+            return Err(CardError::UnknownCard(card));
+        }
+        Ok(value as u32)
+    }
+
+    pub fn is_wildcard(&self, card: char) -> bool {
+        card.is_ascii() && self.wildcards[card as usize]
+    }
+
+    pub fn hand_type(&self, cards: &str) -> Result<HandType, CardError> {
+        Ok(self.hand(cards)?.hand_type())
+    }
+
+    pub fn hand(&self, cards: &str) -> Result<Hand, CardError> {
+        if cards.chars().count() != HAND_SIZE {
+            return Err(CardError::WrongHandSize(cards.to_string()));
+        }
+
+        let mut counts = [0u32; 1 << CARD_BITS];
+        let mut num_wildcards = 0;
+        let mut key = 0;
+        for card in cards.chars() {
+            let value = self.card_value(card)?;
+            key = (key << CARD_BITS) | value;
+            if self.is_wildcard(card) {
+                num_wildcards += 1;
+            } else {
+                counts[value as usize] += 1;
+            }
+        }
+
+        let counts = &mut counts[..self.num_cards];
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        // Wildcards are always best spent on the most common card
+        counts[0] += num_wildcards;
+
+        let hand_type = HandType::from_counts(counts);
+        Ok(Hand {
+            key: ((hand_type as u32) << TYPE_SHIFT) | key,
+        })
+    }
+
+    // Hands and bids, one per line, with the hands checked against these
+    // rules
+    pub fn parse(&self, input: &str) -> Result<Vec<(Hand, u32)>, ParseError> {
+        let syntax = |line: usize, message: String| ParseError::Syntax { line: line + 1, message };
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                let (cards, bid) = line.split_once(' ').ok_or_else(|| syntax(i, "Expected cards and bid".to_string()))?;
+                let hand = self.hand(cards).map_err(|err| syntax(i, format!("Invalid hand: {}", err)))?;
+                let bid = bid.trim().parse::<u32>().map_err(|_| syntax(i, format!("Invalid bid: {}", bid)))?;
+                Ok((hand, bid))
+            })
+            .collect()
+    }
+
+    pub fn total_winnings(&self, input: &str) -> Result<u64, ParseError> {
+        Ok(rank(self.parse(input)?))
+    }
+}
+
+// Sorts the hands weakest first and sums up bid * rank
+pub fn rank(mut hands: Vec<(Hand, u32)>) -> u64 {
+    hands.sort_unstable_by_key(|(hand, _)| hand.key);
+
+    hands
+        .iter()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank as u64 + 1) * *bid as u64)
+        .sum()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand {
+    // Hand type in the top bits, then the card values in the order they
+    // were dealt
+    key: u32,
+}
+impl Hand {
+    pub fn key(&self) -> u32 {
+        self.key
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        HandType::ALL[(self.key >> TYPE_SHIFT) as usize]
+    }
+}
+
+// Deterministic pseudo random hands and bids, so that runs are comparable
+pub fn synthetic_input(num_hands: usize) -> String {
+    const CARDS: &[u8] = b"23456789TJQKA";
+    let mut state: u64 = 0x2023_0007;
+    let mut next = || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    };
+
+    let mut input = String::with_capacity(num_hands * 10);
+    for _ in 0..num_hands {
+        for _ in 0..HAND_SIZE {
+            input.push(CARDS[next() % CARDS.len()] as char);
+        }
+        input.push(' ');
+        input.push_str(&(next() % 1000 + 1).to_string());
+        input.push('\n');
+    }
+    input
+}
+
+// The hands as valued by each part's rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hands {
+    pub standard: Vec<(Hand, u32)>,
+    pub jokers: Vec<(Hand, u32)>,
+}
+
+pub struct Day07;
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Parsed = Hands;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(Hands {
+            standard: Rules::standard().parse(input)?,
+            jokers: Rules::jokers().parse(input)?,
+        })
+    }
+
+    fn part1(hands: &Self::Parsed) -> Answer {
+        rank(hands.standard.clone()).into()
+    }
+
+    fn part2(hands: &Self::Parsed) -> Answer {
+        rank(hands.jokers.clone()).into()
+    }
+}
+
+aoc::register!(Day07);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn card_value_test() {
+        let rules = Rules::standard();
+        assert_eq!(rules.card_value('2'), Ok(0));
+        assert_eq!(rules.card_value('9'), Ok(7));
+        assert_eq!(rules.card_value('T'), Ok(8));
+        assert_eq!(rules.card_value('J'), Ok(9));
+        assert_eq!(rules.card_value('A'), Ok(12));
+
+        let rules = Rules::jokers();
+        assert_eq!(rules.card_value('J'), Ok(0));
+        assert_eq!(rules.card_value('2'), Ok(1));
+        assert_eq!(rules.card_value('A'), Ok(12));
+    }
+
+    #[test]
+    fn from_counts_test() {
+        assert_eq!(HandType::from_counts(&[5]), HandType::FiveOfAKind);
+        assert_eq!(HandType::from_counts(&[4, 1]), HandType::FourOfAKind);
+        assert_eq!(HandType::from_counts(&[3, 2]), HandType::FullHouse);
+        assert_eq!(HandType::from_counts(&[3, 1, 1]), HandType::ThreeOfAKind);
+        assert_eq!(HandType::from_counts(&[2, 2, 1]), HandType::TwoPairs);
+        assert_eq!(HandType::from_counts(&[2, 1, 1, 1]), HandType::OnePair);
+        assert_eq!(HandType::from_counts(&[1, 1, 1, 1, 1]), HandType::HighCard);
+    }
+
+    #[test]
+    fn hand_type_test() {
+        let rules = Rules::standard();
+        assert_eq!(rules.hand_type("AAAAA").unwrap(), HandType::FiveOfAKind);
+        assert_eq!(rules.hand_type("KAAAA").unwrap(), HandType::FourOfAKind);
+        assert_eq!(rules.hand_type("KKKKA").unwrap(), HandType::FourOfAKind);
+        assert_eq!(rules.hand_type("KKAAA").unwrap(), HandType::FullHouse);
+        assert_eq!(rules.hand_type("KKKAA").unwrap(), HandType::FullHouse);
+        assert_eq!(rules.hand_type("QKKKA").unwrap(), HandType::ThreeOfAKind);
+        assert_eq!(rules.hand_type("QQKKA").unwrap(), HandType::TwoPairs);
+        assert_eq!(rules.hand_type("QKKAA").unwrap(), HandType::TwoPairs);
+        assert_eq!(rules.hand_type("JQKKA").unwrap(), HandType::OnePair);
+        assert_eq!(rules.hand_type("JJQKA").unwrap(), HandType::OnePair);
+        assert_eq!(rules.hand_type("TJQKA").unwrap(), HandType::HighCard);
+        assert_eq!(rules.hand_type("KTJJT").unwrap(), HandType::TwoPairs);
+    }
+
+    #[test]
+    fn hand_type_jokers_test() {
+        let rules = Rules::jokers();
+        assert_eq!(rules.hand_type("JJJJJ").unwrap(), HandType::FiveOfAKind);
+        assert_eq!(rules.hand_type("JJJJ2").unwrap(), HandType::FiveOfAKind);
+        assert_eq!(rules.hand_type("JJJ23").unwrap(), HandType::FourOfAKind);
+        assert_eq!(rules.hand_type("JJ223").unwrap(), HandType::FourOfAKind);
+        assert_eq!(rules.hand_type("J2233").unwrap(), HandType::FullHouse);
+        assert_eq!(rules.hand_type("JJ234").unwrap(), HandType::ThreeOfAKind);
+        assert_eq!(rules.hand_type("J2234").unwrap(), HandType::ThreeOfAKind);
+        assert_eq!(rules.hand_type("J2345").unwrap(), HandType::OnePair);
+        assert_eq!(rules.hand_type("KTJJT").unwrap(), HandType::FourOfAKind);
+        assert_eq!(rules.hand_type("T55J5").unwrap(), HandType::FourOfAKind);
+    }
+
+    #[test]
+    fn hand_type_two_wildcards_test() {
+        let rules = Rules::new("JQ23456789TKA", "JQ").unwrap();
+        assert_eq!(rules.hand_type("JQ234").unwrap(), HandType::ThreeOfAKind);
+        assert_eq!(rules.hand_type("JQ224").unwrap(), HandType::FourOfAKind);
+        assert_eq!(rules.hand_type("QQQQJ").unwrap(), HandType::FiveOfAKind);
+        assert!(rules.hand("Q2222").unwrap() > rules.hand("J2222").unwrap());
+    }
+
+    #[test]
+    fn ordering_test() {
+        let rules = Rules::standard();
+        assert!(rules.hand("33332").unwrap() > rules.hand("2AAAA").unwrap());
+        assert!(rules.hand("77888").unwrap() > rules.hand("77788").unwrap());
+        assert!(rules.hand("32T3K").unwrap() < rules.hand("T55J5").unwrap());
+        assert!(rules.hand("T55J5").unwrap() < rules.hand("QQQJA").unwrap());
+        assert!(rules.hand("KK677").unwrap() > rules.hand("KTJJT").unwrap());
+    }
+
+    #[test]
+    fn ordering_jokers_test() {
+        let rules = Rules::jokers();
+        assert!(rules.hand("T55J5").unwrap() < rules.hand("QQQJA").unwrap());
+        assert!(rules.hand("QQQJA").unwrap() < rules.hand("KTJJT").unwrap());
+        assert!(rules.hand("KK677").unwrap() < rules.hand("T55J5").unwrap());
+        assert!(rules.hand("32T3K").unwrap() < rules.hand("KK677").unwrap());
+        assert!(rules.hand("JJJJ2").unwrap() > rules.hand("KTJJT").unwrap());
+        assert!(rules.hand("JJJJJ").unwrap() < rules.hand("22222").unwrap());
+        assert!(rules.hand("JJJJJ").unwrap() > rules.hand("KTJJT").unwrap());
+    }
+
+    #[test]
+    fn key_test() {
+        let rules = Rules::standard();
+        let hand = rules.hand("32T3K").unwrap();
+        assert_eq!(hand.hand_type(), HandType::OnePair);
+        // One pair, then 3 2 T 3 K
+        assert_eq!(hand.key(), 0x1_1081B);
+    }
+
+    #[test]
+    fn rank_test() {
+        let rules = Rules::standard();
+        let hands = vec![
+            (rules.hand("KK677").unwrap(), 3),
+            (rules.hand("32T3K").unwrap(), 1),
+            (rules.hand("KTJJT").unwrap(), 2),
+        ];
+        assert_eq!(rank(hands), 1 + 2 * 2 + 3 * 3);
+    }
+
+    #[test]
+    fn different_card_order_test() {
+        // Aces low
+        let rules = Rules::new("A23456789TJQK", "").unwrap();
+        assert!(rules.hand("A2345").unwrap() < rules.hand("23456").unwrap());
+        assert!(rules.hand("AA234").unwrap() > rules.hand("KQJT9").unwrap());
+    }
+
+    #[test]
+    fn rules_error_test() {
+        assert_eq!(Rules::new("0123456789ABCDEFG", "").unwrap_err(), CardError::TooManyCards(17));
+        assert_eq!(Rules::new("23456789TJQKA", "X").unwrap_err(), CardError::UnknownWildcard('X'));
+        assert!(Rules::new("0123456789ABCDEF", "0").is_ok());
+
+        let rules = Rules::standard();
+        assert_eq!(rules.card_value('X'), Err(CardError::UnknownCard('X')));
+        assert_eq!(rules.card_value('é'), Err(CardError::UnknownCard('é')));
+        assert_eq!(rules.hand("32T3"), Err(CardError::WrongHandSize("32T3".to_string())));
+        assert_eq!(rules.hand("32T3X"), Err(CardError::UnknownCard('X')));
+    }
+
+    #[test]
+    fn parse_test() {
+        let rules = Rules::standard();
+        assert_eq!(
+            Day07::parse("32T3K 765\nKK677 28\n").unwrap().standard,
+            vec![(rules.hand("32T3K").unwrap(), 765), (rules.hand("KK677").unwrap(), 28)]
+        );
+        assert_eq!(
+            Day07::parse("32T3K 765\n32T3 28").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Invalid hand: Hand 32T3 does not have 5 cards".to_string() }
+        );
+        assert!(Day07::parse("32T3K").is_err());
+        assert!(Day07::parse("32T3X 1").is_err());
+
+        // Hands are checked against the rules in use, not a fixed set of cards
+        let rules = Rules::new("0123456789", "0").unwrap();
+        assert!(rules.parse("01234 5").is_ok());
+        assert_eq!(
+            rules.parse("01234 5\n0123A 6").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Invalid hand: Unknown card A".to_string() }
+        );
+    }
+
+    // Ranking a million hands puts them in the same order as comparing hand
+    // types and then card values one by one, with equal keys only for equal
+    // hands. How long it takes is for benches/ranking.rs to show.
+    #[test]
+    fn million_hands_test() {
+        let input = synthetic_input(1_000_000);
+        for rules in [Rules::standard(), Rules::jokers()] {
+            let mut hands: Vec<(Hand, (HandType, [u32; HAND_SIZE]))> = input
+                .lines()
+                .map(|line| {
+                    let cards = &line[..HAND_SIZE];
+                    let mut values = [0; HAND_SIZE];
+                    for (value, card) in values.iter_mut().zip(cards.chars()) {
+                        *value = rules.card_value(card).unwrap();
+                    }
+                    let hand = rules.hand(cards).unwrap();
+                    (hand, (hand.hand_type(), values))
+                })
+                .collect();
+            assert_eq!(hands.len(), 1_000_000);
+
+            hands.sort_unstable_by_key(|(hand, _)| hand.key());
+            for pair in hands.windows(2) {
+                let ((a, a_cards), (b, b_cards)) = (&pair[0], &pair[1]);
+                assert!(a_cards <= b_cards, "{:?} ranks below {:?}", a_cards, b_cards);
+                assert_eq!(a == b, a_cards == b_cards);
+            }
+        }
+    }
+}
@@ -1,87 +1,19 @@
-use std::collections::HashMap;
-
-use nom::{
-    bytes::complete::tag,
-    sequence::tuple,
-    character::complete::alpha1
-};
-
-use nom_supreme::error::ErrorTree;
+use aoc::{Answer, Solution};
+use day_08::{parse, to_dot, Day08};
 
 fn main() {
     let input = include_str!("../../input.txt");
+    // With --dot, print the network as a Graphviz graph instead of solving it
+    if std::env::args().any(|arg| arg == "--dot") {
+        print!("{}", to_dot(&parse(input).1));
+        return;
+    }
     let output = part1(input);
     dbg!(output);
 }
 
-#[derive(Debug)]
-struct Node<'a> {
-    left: &'a str,
-    right: &'a str,
-}
-
-#[derive(Debug)]
-struct NamedNode<'a> {
-    name: &'a str,
-    node: Node<'a>,
-}
-
-fn node_parser(input: &str) -> nom::IResult<&str, NamedNode, ErrorTree<&str>> {
-    let (input, name) = alpha1(input)?;
-    let (input, _) = tag(" = ")(input)?;
-    let (input, (_, left, _, right, _)) = tuple((tag("("), alpha1, tag(", "), alpha1, tag(")")))(input)?;
-    Ok(
-        (
-            input,
-            NamedNode {
-                name: name,
-                node: Node {
-                    left: left,
-                    right: right,
-                }
-            }
-        )
-    )
-}
-
-fn part1(input: &str) -> String {
-    let mut lines = input.lines();
-    let instructions = lines.next().unwrap();
-    println!("{}", instructions);
-
-    // Skip the empty line
-    lines.next();
-
-    let mut nodes: HashMap<&str, Node> = HashMap::new();
-    for line in lines {
-        let (_, nn) = node_parser(line).unwrap();
-        nodes.insert(nn.name, nn.node);
-    }
-
-    println!("{:?}", nodes);
-
-    let mut steps = 0;
-    let mut curr_node = "AAA";
-    loop {
-        for instruction in instructions.chars() {
-            match instruction {
-                'L' => {
-                    curr_node = nodes.get(curr_node).unwrap().left;
-                },
-                'R' => {
-                    curr_node = nodes.get(curr_node).unwrap().right;
-                },
-                _ => {
-                    panic!("Unknown instruction: {}", instruction);
-                }
-            }
-            steps += 1;
-            println!("{}: {}", steps, curr_node);
-            if curr_node == "ZZZ" {
-                return steps.to_string();
-            }
-        }
-    }
+fn part1(input: &str) -> Answer {
+    Day08::part1(&Day08::parse(input).unwrap())
 }
 
 #[cfg(test)]
@@ -97,6 +29,6 @@ ZZZ = (ZZZ, ZZZ)";
     #[test]
     fn it_works1() {
         let result = part1(INPUT);
-        assert_eq!(result, "6".to_string());
+        assert_eq!(result, Answer::UInt(6));
     }
 }
\ No newline at end of file
@@ -0,0 +1,310 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use nom::{character::complete::alphanumeric1, sequence::tuple};
+use nom_supreme::error::ErrorTree;
+use nom_supreme::tag::complete::tag;
+
+use aoc::{Answer, ParseError, Solution};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node<'a> {
+    pub left: &'a str,
+    pub right: &'a str,
+}
+
+#[derive(Debug)]
+pub struct NamedNode<'a> {
+    pub name: &'a str,
+    pub node: Node<'a>,
+}
+
+pub fn node_parser(input: &str) -> nom::IResult<&str, NamedNode<'_>, ErrorTree<&str>> {
+    let (input, name) = alphanumeric1(input)?;
+    let (input, _) = tag(" = ")(input)?;
+    let (input, (_, left, _, right, _)) = tuple((tag("("), alphanumeric1, tag(", "), alphanumeric1, tag(")")))(input)?;
+    Ok((input, NamedNode { name, node: Node { left, right } }))
+}
+
+// The instructions on the first line and the nodes after the empty line
+pub fn parse(input: &str) -> (&str, HashMap<&str, Node<'_>>) {
+    let mut lines = input.lines();
+    let instructions = lines.next().unwrap();
+
+    // Skip the empty line
+    lines.next();
+
+    let mut nodes = HashMap::new();
+    for line in lines {
+        let (_, nn) = node_parser(line).unwrap();
+        nodes.insert(nn.name, nn.node);
+    }
+
+    (instructions, nodes)
+}
+
+// The network as a Graphviz graph, with start nodes (..A) in green and end
+// nodes (..Z) in red. A node that goes the same way on L and R gets a single
+// L/R edge.
+pub fn to_dot(nodes: &HashMap<&str, Node>) -> String {
+    let mut names: Vec<&str> = nodes.keys().copied().collect();
+    names.sort_unstable();
+
+    let mut dot = String::from("digraph network {\n");
+    for name in &names {
+        if name.ends_with('A') {
+            writeln!(dot, "    \"{}\" [style=filled, fillcolor=palegreen];", name).unwrap();
+        } else if name.ends_with('Z') {
+            writeln!(dot, "    \"{}\" [style=filled, fillcolor=lightcoral];", name).unwrap();
+        }
+    }
+    for name in &names {
+        let node = &nodes[name];
+        if node.left == node.right {
+            writeln!(dot, "    \"{}\" -> \"{}\" [label=\"L/R\"];", name, node.left).unwrap();
+        } else {
+            writeln!(dot, "    \"{}\" -> \"{}\" [label=\"L\"];", name, node.left).unwrap();
+            writeln!(dot, "    \"{}\" -> \"{}\" [label=\"R\"];", name, node.right).unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+pub fn factorize(n: usize) -> Vec<usize> {
+    let mut factors: Vec<usize> = Vec::new();
+    let mut n = n;
+    let mut i = 2;
+    while i * i <= n {
+        while n.is_multiple_of(i) {
+            factors.push(i);
+            n /= i;
+        }
+        i += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors.sort();
+    factors
+}
+
+// The network with every node referred to by its index in names, so it
+// doesn't borrow from the input
+#[derive(Debug, PartialEq, Eq)]
+pub struct Network {
+    pub instructions: String,
+    pub names: Vec<String>,
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+}
+impl Network {
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    // Follows the instructions from start, over and over, until reaching a
+    // node whose name is_end accepts
+    pub fn steps(&self, start: usize, is_end: impl Fn(&str) -> bool) -> usize {
+        let mut steps = 0;
+        let mut curr_node = start;
+        for instruction in self.instructions.chars().cycle() {
+            curr_node = match instruction {
+                'L' => self.left[curr_node],
+                _ => self.right[curr_node],
+            };
+            steps += 1;
+            if is_end(&self.names[curr_node]) {
+                break;
+            }
+        }
+        steps
+    }
+}
+
+pub struct Day08;
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Parsed = Network;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut lines = input.lines();
+        let instructions = lines.next().unwrap_or("");
+        if instructions.is_empty() || !instructions.chars().all(|c| c == 'L' || c == 'R') {
+            return Err(ParseError::Syntax { line: 1, message: "Expected L and R instructions".to_string() });
+        }
+
+        // Skip the empty line
+        lines.next();
+
+        let mut named_nodes = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let (_, nn) = node_parser(line).map_err(|_| ParseError::Syntax {
+                line: i + 3,
+                message: format!("Expected a node: {}", line),
+            })?;
+            named_nodes.push(nn);
+        }
+        named_nodes.sort_unstable_by_key(|nn| nn.name);
+
+        let id = |name: &str| {
+            named_nodes
+                .binary_search_by_key(&name, |nn| nn.name)
+                .map_err(|_| ParseError::Invalid(format!("Unknown node: {}", name)))
+        };
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        for nn in &named_nodes {
+            left.push(id(nn.node.left)?);
+            right.push(id(nn.node.right)?);
+        }
+
+        Ok(Network {
+            instructions: instructions.to_string(),
+            names: named_nodes.iter().map(|nn| nn.name.to_string()).collect(),
+            left,
+            right,
+        })
+    }
+
+    fn part1(network: &Self::Parsed) -> Answer {
+        // The ghost-only example networks have no AAA to start from
+        let Some(start) = network.id("AAA") else {
+            return Answer::Unsolved;
+        };
+        network.steps(start, |name| name == "ZZZ").into()
+    }
+
+    fn part2(network: &Self::Parsed) -> Answer {
+        // Every start node reaches its end node in a cycle of the same length,
+        // so they all meet up at the product of the cycles' prime factors
+        let mut prod: usize = 1;
+        let mut factors: HashSet<usize> = HashSet::new();
+        for (start, name) in network.names.iter().enumerate() {
+            if !name.ends_with('A') {
+                continue;
+            }
+            let count = network.steps(start, |name| name.ends_with('Z'));
+            for f in factorize(count) {
+                if factors.insert(f) {
+                    prod *= f;
+                }
+            }
+        }
+
+        prod.into()
+    }
+}
+
+aoc::register!(Day08);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let (instructions, nodes) = parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)");
+        assert_eq!(instructions, "LLR");
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes["BBB"], Node { left: "AAA", right: "ZZZ" });
+    }
+
+    #[test]
+    fn test_to_dot_part1() {
+        let (_, nodes) = parse(
+            "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)",
+        );
+        assert_eq!(
+            to_dot(&nodes),
+            r#"digraph network {
+    "AAA" [style=filled, fillcolor=palegreen];
+    "ZZZ" [style=filled, fillcolor=lightcoral];
+    "AAA" -> "BBB" [label="L"];
+    "AAA" -> "CCC" [label="R"];
+    "BBB" -> "DDD" [label="L"];
+    "BBB" -> "EEE" [label="R"];
+    "CCC" -> "ZZZ" [label="L"];
+    "CCC" -> "GGG" [label="R"];
+    "DDD" -> "DDD" [label="L/R"];
+    "EEE" -> "EEE" [label="L/R"];
+    "GGG" -> "GGG" [label="L/R"];
+    "ZZZ" -> "ZZZ" [label="L/R"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_to_dot_part2() {
+        let (_, nodes) = parse(
+            "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+        );
+        assert_eq!(
+            to_dot(&nodes),
+            r#"digraph network {
+    "11A" [style=filled, fillcolor=palegreen];
+    "11Z" [style=filled, fillcolor=lightcoral];
+    "22A" [style=filled, fillcolor=palegreen];
+    "22Z" [style=filled, fillcolor=lightcoral];
+    "11A" -> "11B" [label="L"];
+    "11A" -> "XXX" [label="R"];
+    "11B" -> "XXX" [label="L"];
+    "11B" -> "11Z" [label="R"];
+    "11Z" -> "11B" [label="L"];
+    "11Z" -> "XXX" [label="R"];
+    "22A" -> "22B" [label="L"];
+    "22A" -> "XXX" [label="R"];
+    "22B" -> "22C" [label="L/R"];
+    "22C" -> "22Z" [label="L/R"];
+    "22Z" -> "22B" [label="L/R"];
+    "XXX" -> "XXX" [label="L/R"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_network() {
+        let network = Day08::parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(network.names, vec!["AAA", "BBB", "ZZZ"]);
+        assert_eq!(network.left, vec![1, 0, 2]);
+        assert_eq!(network.right, vec![1, 2, 2]);
+        assert_eq!(network.steps(0, |name| name == "ZZZ"), 6);
+
+        assert_eq!(
+            Day08::parse("LLR\n\nAAA = (BBB, BBB)\nBBB = AAA").unwrap_err(),
+            ParseError::Syntax { line: 4, message: "Expected a node: BBB = AAA".to_string() }
+        );
+        assert_eq!(Day08::parse("LLR\n\nAAA = (BBB, BBB)").unwrap_err(), ParseError::Invalid("Unknown node: BBB".to_string()));
+        assert!(Day08::parse("LXR\n\nAAA = (AAA, AAA)").is_err());
+
+        let ghosts = Day08::parse("L\n\n11A = (11Z, 11Z)\n11Z = (11Z, 11Z)").unwrap();
+        assert_eq!(Day08::part1(&ghosts), Answer::Unsolved);
+        assert_eq!(Day08::part2(&ghosts), Answer::UInt(1));
+    }
+
+    #[test]
+    fn fact_test() {
+        assert_eq!(factorize(24253), vec![79, 307]);
+        assert_eq!(factorize(12), vec![2, 2, 3]);
+        assert_eq!(factorize(13), vec![13]);
+    }
+}
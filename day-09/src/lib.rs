@@ -0,0 +1,249 @@
+use std::fmt;
+
+use aoc::{Answer, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    // Taking differences, at the given depth. The differences between the
+    // values themselves are at depth 1.
+    Difference { depth: usize },
+    // Evaluating the polynomial at the given index
+    Value { index: i64 },
+    // Adding up the values of all the histories
+    Sum,
+}
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Overflow::Difference { depth } => write!(f, "difference at depth {} does not fit in an i128", depth),
+            Overflow::Value { index } => write!(f, "value at index {} does not fit in an i128", index),
+            Overflow::Sum => write!(f, "sum does not fit in an i128"),
+        }
+    }
+}
+impl std::error::Error for Overflow {}
+
+pub fn parse_input(input: &str) -> Vec<i64> {
+    input
+        .split_whitespace()
+        .map(|word| word.parse::<i64>().unwrap())
+        .collect()
+}
+
+// The exact polynomial through a history of values, stored in Newton forward
+// difference form: p(x) = sum over k of coefficients[k] * binomial(x, k),
+// where x is the index into the history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<i128>,
+}
+impl Polynomial {
+    pub fn fit(history: &[i64]) -> Result<Self, Overflow> {
+        let mut row: Vec<i128> = history.iter().map(|value| *value as i128).collect();
+        let mut coefficients = Vec::new();
+
+        while row.iter().any(|value| *value != 0) {
+            coefficients.push(row[0]);
+            let overflow = Overflow::Difference { depth: coefficients.len() };
+            row = row
+                .windows(2)
+                .map(|pair| pair[1].checked_sub(pair[0]).ok_or(overflow))
+                .collect::<Result<_, _>>()?;
+        }
+
+        Ok(Self { coefficients })
+    }
+
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn coefficients(&self) -> &[i128] {
+        &self.coefficients
+    }
+
+    // Index 0 is the first value of the history, so a history of length n is
+    // extrapolated one step forward at n and one step backward at -1.
+    pub fn value_at(&self, index: i64) -> Result<i128, Overflow> {
+        let overflow = Overflow::Value { index };
+        let x = index as i128;
+
+        let mut value: i128 = 0;
+        // binomial(x, k), which is well defined for negative x as well
+        let mut binomial: i128 = 1;
+        for (k, coefficient) in self.coefficients.iter().enumerate() {
+            if k > 0 {
+                // binomial(x, k) = binomial(x, k - 1) * (x - k + 1) / k, and
+                // the division is always exact
+                binomial = binomial
+                    .checked_mul(x - k as i128 + 1)
+                    .ok_or(overflow)?
+                    / k as i128;
+            }
+            let term = coefficient.checked_mul(binomial).ok_or(overflow)?;
+            value = value.checked_add(term).ok_or(overflow)?;
+        }
+
+        Ok(value)
+    }
+}
+
+// The sum of every history's polynomial at the index at picks for it, with
+// the line of the history where that overflows
+pub fn extrapolate(histories: &[Vec<i64>], at: impl Fn(&[i64]) -> i64) -> Result<i128, (usize, Overflow)> {
+    let mut total: i128 = 0;
+    for (i, history) in histories.iter().enumerate() {
+        let value = Polynomial::fit(history).and_then(|polynomial| polynomial.value_at(at(history)));
+        total = value.and_then(|value| total.checked_add(value).ok_or(Overflow::Sum)).map_err(|overflow| (i + 1, overflow))?;
+    }
+    Ok(total)
+}
+
+pub struct Day09;
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    // One history per line
+    type Parsed = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut histories = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let syntax = |message: String| ParseError::Syntax { line: i + 1, message };
+            let history = line
+                .split_whitespace()
+                .map(|word| word.parse::<i64>().map_err(|_| syntax(format!("Invalid value: {}", word))))
+                .collect::<Result<Vec<_>, _>>()?;
+            // Every history has to have a polynomial to extrapolate
+            Polynomial::fit(&history).map_err(|overflow| syntax(overflow.to_string()))?;
+            histories.push(history);
+        }
+        Ok(histories)
+    }
+
+    fn part1(histories: &Self::Parsed) -> Answer {
+        answer(extrapolate(histories, |history| history.len() as i64))
+    }
+
+    fn part2(histories: &Self::Parsed) -> Answer {
+        answer(extrapolate(histories, |_| -1))
+    }
+}
+
+// An overflow has no number to give, so the answer says where it happened
+fn answer(total: Result<i128, (usize, Overflow)>) -> Answer {
+    match total {
+        Ok(total) => total.into(),
+        Err((line, overflow)) => Answer::Text(format!("Overflow on line {}: {}", line, overflow)),
+    }
+}
+
+aoc::register!(Day09);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn parse_input_test() {
+        let result: Vec<Vec<i64>> = INPUT.lines().map(parse_input).collect();
+        assert_eq!(result, vec![
+            vec![0, 3, 6, 9, 12, 15],
+            vec![1, 3, 6, 10, 15, 21],
+            vec![10, 13, 16, 21, 30, 45],
+        ]);
+    }
+
+    #[test]
+    fn fit_test() {
+        let polynomial = Polynomial::fit(&[0, 3, 6, 9, 12, 15]).unwrap();
+        assert_eq!(polynomial.coefficients(), &[0, 3]);
+        assert_eq!(polynomial.degree(), Some(1));
+
+        let polynomial = Polynomial::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(polynomial.coefficients(), &[10, 3, 0, 2]);
+        assert_eq!(polynomial.degree(), Some(3));
+
+        let polynomial = Polynomial::fit(&[0, 0, 0]).unwrap();
+        assert_eq!(polynomial.degree(), None);
+        assert_eq!(polynomial.value_at(100), Ok(0));
+    }
+
+    #[test]
+    fn value_at_test() {
+        let polynomial = Polynomial::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+        for (index, value) in [10, 13, 16, 21, 30, 45].iter().enumerate() {
+            assert_eq!(polynomial.value_at(index as i64), Ok(*value));
+        }
+        assert_eq!(polynomial.value_at(6), Ok(68));
+        assert_eq!(polynomial.value_at(-1), Ok(5));
+    }
+
+    #[test]
+    fn value_at_far_offsets_test() {
+        // 1, 3, 6, 10, ... are the triangular numbers (x + 1)(x + 2) / 2
+        let polynomial = Polynomial::fit(&[1, 3, 6, 10, 15, 21]).unwrap();
+        assert_eq!(polynomial.value_at(1000), Ok(1001 * 1002 / 2));
+        assert_eq!(polynomial.value_at(-1_000_000), Ok(999_999 * 999_998 / 2));
+
+        // x^3 - 2x at x = -10^6
+        let history: Vec<i64> = (0..8).map(|x| x * x * x - 2 * x).collect();
+        let polynomial = Polynomial::fit(&history).unwrap();
+        assert_eq!(polynomial.value_at(-1_000_000), Ok(-1_000_000_000_000_000_000 + 2_000_000));
+    }
+
+    #[test]
+    fn overflow_test() {
+        // x^6 no longer fits in an i128 at x = 10^7
+        let history: Vec<i64> = (0..8).map(|x: i64| x.pow(6)).collect();
+        let polynomial = Polynomial::fit(&history).unwrap();
+        assert_eq!(polynomial.value_at(1_000), Ok(1_000_000_000_000_000_000));
+        assert_eq!(polynomial.value_at(10_000_000), Err(Overflow::Value { index: 10_000_000 }));
+
+        // Alternating extremes double their differences at every depth
+        let history: Vec<i64> = (0..70).map(|x| if x % 2 == 0 { i64::MAX } else { i64::MIN }).collect();
+        assert_eq!(Polynomial::fit(&history), Err(Overflow::Difference { depth: 65 }));
+    }
+
+    #[test]
+    fn extrapolate_overflow_test() {
+        let big = vec![i64::MAX, i64::MAX];
+        let histories = vec![vec![1, 2], big.clone(), big.clone()];
+        assert_eq!(extrapolate(&histories, |_| -1), Ok(2 * i64::MAX as i128));
+
+        let histories = vec![vec![0, 0], vec![0, i64::MAX, 0]];
+        assert_eq!(extrapolate(&histories, |_| i64::MAX), Err((2, Overflow::Value { index: i64::MAX })));
+
+        // Each of these comes to a bit under 2^125 at 2^62
+        let histories = vec![vec![i64::MIN, 0]; 8];
+        assert_eq!(extrapolate(&histories, |_| 1 << 62), Err((5, Overflow::Sum)));
+
+        let overflowing: Vec<i64> = (0..70).map(|x| if x % 2 == 0 { i64::MAX } else { i64::MIN }).collect();
+        assert_eq!(
+            Day09::part1(&vec![vec![0, 0], overflowing.clone()]),
+            Answer::Text("Overflow on line 2: difference at depth 65 does not fit in an i128".to_string())
+        );
+        assert_eq!(
+            Day09::part2(&vec![overflowing]),
+            Answer::Text("Overflow on line 1: difference at depth 65 does not fit in an i128".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Day09::parse("0 3 6\n-1 2").unwrap(), vec![vec![0, 3, 6], vec![-1, 2]]);
+        assert_eq!(
+            Day09::parse("0 3 6\n1 x").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Invalid value: x".to_string() }
+        );
+
+        let overflowing: Vec<String> = (0..70).map(|x| if x % 2 == 0 { i64::MAX } else { i64::MIN }.to_string()).collect();
+        assert_eq!(
+            Day09::parse(&format!("0 3 6\n{}", overflowing.join(" "))).unwrap_err(),
+            ParseError::Syntax { line: 2, message: "difference at depth 65 does not fit in an i128".to_string() }
+        );
+    }
+}
@@ -0,0 +1,631 @@
+use std::fmt;
+
+use aoc::{Answer, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pipe {
+    Empty = 0,
+    Vertical,
+    Horizontal,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pos {
+    pub x: i64,
+    pub y: i64,
+}
+
+pub fn pipe_to_char(pipe: Pipe) -> char {
+    match pipe {
+        Pipe::Empty => '.',
+        Pipe::Vertical => '|',
+        Pipe::Horizontal => '-',
+        Pipe::NorthEast => 'L',
+        Pipe::NorthWest => 'J',
+        Pipe::SouthEast => 'F',
+        Pipe::SouthWest => '7',
+        Pipe::Unknown => 'S',
+    }
+}
+
+pub fn pipe_directions(pipe: Pipe) -> (Pos, Pos) {
+    match pipe {
+        // Fail horribly
+        Pipe::Empty => panic!("Empty pipe!"),
+        Pipe::Vertical => (Pos { x: 0, y: 1 }, Pos { x: 0, y: -1 }),
+        Pipe::Horizontal => (Pos { x: -1, y: 0 }, Pos { x: 1, y: 0 }),
+        Pipe::NorthEast => (Pos { x: 1, y: 0 }, Pos { x: 0, y: -1 }),
+        Pipe::NorthWest => (Pos { x: -1, y: 0 }, Pos { x: 0, y: -1 }),
+        Pipe::SouthEast => (Pos { x: 0, y: 1 }, Pos { x: 1, y: 0 }),
+        Pipe::SouthWest => (Pos { x: 0, y: 1 }, Pos { x: -1, y: 0 }),
+        // Fail horribly
+        Pipe::Unknown => panic!("Unknown pipe!"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    UnknownCharacter { c: char, pos: Pos },
+    NoStart,
+    MultipleStarts { first: Pos, second: Pos },
+    StartConnections { pos: Pos, connections: Vec<Pos> },
+    RaggedRow { y: i64, length: usize, expected: usize },
+    OpenLoop { at: Pos },
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownCharacter { c, pos } => {
+                write!(f, "unknown character {:?} at ({}, {})", c, pos.x, pos.y)
+            }
+            ParseError::NoStart => write!(f, "no start tile S in the input"),
+            ParseError::MultipleStarts { first, second } => write!(
+                f,
+                "more than one start tile, at ({}, {}) and ({}, {})",
+                first.x, first.y, second.x, second.y
+            ),
+            ParseError::StartConnections { pos, connections } => {
+                let names: Vec<&str> = connections.iter().map(|dir| direction_name(*dir)).collect();
+                write!(
+                    f,
+                    "start tile at ({}, {}) has {} neighbours connecting back to it [{}], expected exactly 2",
+                    pos.x,
+                    pos.y,
+                    connections.len(),
+                    names.join(", ")
+                )
+            }
+            ParseError::RaggedRow { y, length, expected } => {
+                write!(f, "row {} is {} tiles long, expected {}", y, length, expected)
+            }
+            ParseError::OpenLoop { at } => {
+                write!(f, "the loop from the start tile breaks off at ({}, {})", at.x, at.y)
+            }
+        }
+    }
+}
+impl std::error::Error for ParseError {}
+
+const NORTH: Pos = Pos { x: 0, y: -1 };
+const SOUTH: Pos = Pos { x: 0, y: 1 };
+const EAST: Pos = Pos { x: 1, y: 0 };
+const WEST: Pos = Pos { x: -1, y: 0 };
+
+const PIPES: [Pipe; 6] = [
+    Pipe::Vertical,
+    Pipe::Horizontal,
+    Pipe::NorthEast,
+    Pipe::NorthWest,
+    Pipe::SouthEast,
+    Pipe::SouthWest,
+];
+
+fn direction_name(dir: Pos) -> &'static str {
+    match dir {
+        NORTH => "north",
+        SOUTH => "south",
+        EAST => "east",
+        _ => "west",
+    }
+}
+
+// Whether the pipe has an opening towards the given direction
+pub fn pipe_connects(pipe: Pipe, dir: Pos) -> bool {
+    match pipe {
+        Pipe::Empty | Pipe::Unknown => false,
+        _ => {
+            let dirs = pipe_directions(pipe);
+            dirs.0 == dir || dirs.1 == dir
+        }
+    }
+}
+
+// Works out which pipe is hidden under the start tile by checking which of
+// its neighbours have an opening pointing back at it
+pub fn infer_start_pipe(pipes: &[Vec<Pipe>], start: Pos) -> Result<Pipe, ParseError> {
+    let mut connections = Vec::new();
+    for dir in [NORTH, SOUTH, EAST, WEST] {
+        let x = start.x + dir.x;
+        let y = start.y + dir.y;
+        if y < 0 || y as usize >= pipes.len() || x < 0 || x as usize >= pipes[y as usize].len() {
+            continue;
+        }
+
+        let opposite = Pos { x: -dir.x, y: -dir.y };
+        if pipe_connects(pipes[y as usize][x as usize], opposite) {
+            connections.push(dir);
+        }
+    }
+
+    if connections.len() == 2 {
+        for pipe in PIPES {
+            if pipe_connects(pipe, connections[0]) && pipe_connects(pipe, connections[1]) {
+                return Ok(pipe);
+            }
+        }
+    }
+
+    Err(ParseError::StartConnections { pos: start, connections })
+}
+
+// The tiles as they are in the input, with the start tile still Unknown
+pub fn parse_grid(input: &str) -> Result<(Vec<Vec<Pipe>>, Pos), ParseError> {
+    let mut pipes: Vec<Vec<Pipe>> = Vec::new();
+    let mut start: Option<Pos> = None;
+    for (y, line) in input.lines().enumerate() {
+        let mut row: Vec<Pipe> = Vec::new();
+        for (x, c) in line.chars().enumerate() {
+            let pos = Pos { x: x as i64, y: y as i64 };
+            row.push(match c {
+                '.' => Pipe::Empty,
+                '|' => Pipe::Vertical,
+                '-' => Pipe::Horizontal,
+                'L' => Pipe::NorthEast,
+                'J' => Pipe::NorthWest,
+                'F' => Pipe::SouthEast,
+                '7' => Pipe::SouthWest,
+                'S' => Pipe::Unknown,
+                _ => return Err(ParseError::UnknownCharacter { c, pos }),
+            });
+
+            if c == 'S' {
+                if let Some(first) = start {
+                    return Err(ParseError::MultipleStarts { first, second: pos });
+                }
+                start = Some(pos);
+            }
+        }
+        if let Some(first) = pipes.first() {
+            if row.len() != first.len() {
+                return Err(ParseError::RaggedRow { y: y as i64, length: row.len(), expected: first.len() });
+            }
+        }
+        pipes.push(row);
+    }
+
+    let start = start.ok_or(ParseError::NoStart)?;
+    Ok((pipes, start))
+}
+
+// The tiles with the pipe under the start tile filled in. Only succeeds if
+// the start is on a closed loop, so that the loop can be followed without
+// leaving the grid.
+pub fn parse_pipes(input: &str) -> Result<(Vec<Vec<Pipe>>, Pos), ParseError> {
+    let (mut pipes, start) = parse_grid(input)?;
+    pipes[start.y as usize][start.x as usize] = infer_start_pipe(&pipes, start)?;
+    check_loop(&pipes, start)?;
+
+    Ok((pipes, start))
+}
+
+// Follows the loop from the start tile the way visit_pipes does, making sure
+// every pipe leads to one that connects back to it, until it's back at the
+// start. A loop that breaks off fails at its last tile.
+fn check_loop(pipes: &[Vec<Pipe>], start: Pos) -> Result<(), ParseError> {
+    let mut pos = start;
+    let mut next_dir = pipe_directions(pipes[pos.y as usize][pos.x as usize]).0;
+    loop {
+        let next = Pos { x: pos.x + next_dir.x, y: pos.y + next_dir.y };
+        let opposite = Pos { x: -next_dir.x, y: -next_dir.y };
+        let in_grid =
+            next.y >= 0 && (next.y as usize) < pipes.len() && next.x >= 0 && (next.x as usize) < pipes[0].len();
+        if !in_grid || !pipe_connects(pipes[next.y as usize][next.x as usize], opposite) {
+            return Err(ParseError::OpenLoop { at: pos });
+        }
+        if next == start {
+            return Ok(());
+        }
+
+        let dirs = pipe_directions(pipes[next.y as usize][next.x as usize]);
+        next_dir = if dirs.0 == opposite { dirs.1 } else { dirs.0 };
+        pos = next;
+    }
+}
+
+// Follows the loop from the start tile and returns every tile on it, in the
+// order they are visited. The start tile comes first and is not repeated at
+// the end.
+pub fn visit_pipes(pipes: &[Vec<Pipe>], start: Pos) -> Vec<Pos> {
+    let mut vertices = vec![start];
+    let mut pos = start;
+    let mut next_dir = pipe_directions(pipes[pos.y as usize][pos.x as usize]).0;
+    loop {
+        let old_pos = pos;
+
+        // Move to next position
+        pos.x += next_dir.x;
+        pos.y += next_dir.y;
+
+        if pos == start {
+            // We're done
+            break;
+        }
+        vertices.push(pos);
+
+        // Continue out through whichever end of the pipe we didn't come in by
+        let dirs = pipe_directions(pipes[pos.y as usize][pos.x as usize]);
+        if (Pos { x: pos.x + dirs.0.x, y: pos.y + dirs.0.y }) == old_pos {
+            next_dir = dirs.1;
+        } else {
+            next_dir = dirs.0;
+        }
+    }
+
+    vertices
+}
+
+// Twice the area of the polygon through the tile centers, using the
+// shoelace formula
+pub fn double_area(vertices: &[Pos]) -> i64 {
+    let mut sum = 0;
+    for (i, a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        sum += a.x * b.y - b.x * a.y;
+    }
+    sum.abs()
+}
+
+// Pick's theorem, A = i + b/2 - 1, where every loop tile is a boundary point
+// and the enclosed tiles are the interior points
+pub fn calculate_enclosure(vertices: &[Pos]) -> i64 {
+    let boundary = vertices.len() as i64;
+    (double_area(vertices) - boundary) / 2 + 1
+}
+
+// Walks the loop from the start in one of its two directions, marking how
+// far each tile is until reaching tiles already found closer the other way
+fn update_distances(pipes: &[Vec<Pipe>], start: Pos, direction: i32, distances: &mut [Vec<i64>]) -> i64 {
+    let mut pos = Pos { x: start.x, y: start.y };
+    let mut p = pipes[pos.y as usize][pos.x as usize];
+
+    let dists = pipe_directions(p);
+    let mut next_dir;
+
+    if direction == 0 {
+        next_dir = dists.0;
+    } else {
+        next_dir = dists.1;
+    }
+
+    let mut dist = 0;
+    loop {
+        let old_pos = Pos { x: pos.x, y: pos.y };
+
+        // Move to next position
+        pos.x += next_dir.x;
+        pos.y += next_dir.y;
+
+        if pos == start {
+            // We're done
+            break;
+        }
+
+        dist += 1;
+        if distances[pos.y as usize][pos.x as usize] != -1 && distances[pos.y as usize][pos.x as usize] <= dist {
+            // We've already been here, and we've already found a shorter path
+            break;
+        }
+        distances[pos.y as usize][pos.x as usize] = dist;
+
+        // Get pipe at new position
+        p = pipes[pos.y as usize][pos.x as usize];
+
+        // Get directions of pipe
+        let dirs = pipe_directions(p);
+
+        if (Pos { x: pos.x + dirs.0.x, y: pos.y + dirs.0.y }) == old_pos {
+            next_dir = dirs.1;
+        } else {
+            next_dir = dirs.0;
+        }
+    }
+
+    dist
+}
+
+// How far along the loop the furthest tile is, and the distance to every
+// tile, with -1 for those not on the loop
+pub fn calculate_distances(pipes: &[Vec<Pipe>], start: Pos) -> (i64, Vec<Vec<i64>>) {
+    let mut distances: Vec<Vec<i64>> = vec![vec![-1; pipes[0].len()]; pipes.len()];
+
+    let pos = Pos { x: start.x, y: start.y };
+    distances[pos.y as usize][pos.x as usize] = 0;
+
+    update_distances(pipes, start, 0, &mut distances);
+    let dist = update_distances(pipes, start, 1, &mut distances);
+
+    (dist, distances)
+}
+
+impl From<ParseError> for aoc::ParseError {
+    fn from(error: ParseError) -> Self {
+        aoc::ParseError::Invalid(error.to_string())
+    }
+}
+
+pub struct Day10;
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Parsed = (Vec<Vec<Pipe>>, Pos);
+
+    fn parse(input: &str) -> Result<Self::Parsed, aoc::ParseError> {
+        Ok(parse_pipes(input)?)
+    }
+
+    fn part1((pipes, pos): &Self::Parsed) -> Answer {
+        let (dist, _distances) = calculate_distances(pipes, *pos);
+        dist.into()
+    }
+
+    fn part2((pipes, pos): &Self::Parsed) -> Answer {
+        let vertices = visit_pipes(pipes, *pos);
+        calculate_enclosure(&vertices).into()
+    }
+}
+
+aoc::register!(Day10);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_SIMPLER: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const INPUT_JUNK: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    const INPUT_SQUEEZE: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    // Counts enclosed tiles by casting a ray along each row and counting how
+    // many times it crosses the loop. Only pipes with a northern connection
+    // count as crossings, so a ray running along a -- segment isn't confused.
+    fn calculate_enclosure_by_ray_casting(pipes: &[Vec<Pipe>], vertices: &[Pos]) -> i64 {
+        let mut on_loop = vec![vec![false; pipes[0].len()]; pipes.len()];
+        for pos in vertices {
+            on_loop[pos.y as usize][pos.x as usize] = true;
+        }
+
+        let mut enclosed = 0;
+        for (y, row) in pipes.iter().enumerate() {
+            let mut crosses = 0;
+            for (x, pipe) in row.iter().enumerate() {
+                if !on_loop[y][x] {
+                    if crosses % 2 == 1 {
+                        enclosed += 1;
+                    }
+                } else if matches!(pipe, Pipe::Vertical | Pipe::NorthEast | Pipe::NorthWest) {
+                    crosses += 1;
+                }
+            }
+        }
+
+        enclosed
+    }
+
+    fn enclosure_test(input: &str, expected: i64) {
+        let (pipes, pos) = parse_pipes(input).unwrap();
+        let vertices = visit_pipes(&pipes, pos);
+        assert_eq!(calculate_enclosure(&vertices), expected);
+        assert_eq!(calculate_enclosure_by_ray_casting(&pipes, &vertices), expected);
+    }
+
+    #[test]
+    fn parse_pipes_test1() {
+        let result = parse_pipes(".....
+.S-7.
+.|.|.
+.L-J.
+.....");
+        assert_eq!(
+            result,
+            Ok((
+                vec![
+                    vec![Pipe::Empty, Pipe::Empty, Pipe::Empty, Pipe::Empty, Pipe::Empty],
+                    vec![Pipe::Empty, Pipe::SouthEast, Pipe::Horizontal, Pipe::SouthWest, Pipe::Empty],
+                    vec![Pipe::Empty, Pipe::Vertical, Pipe::Empty, Pipe::Vertical, Pipe::Empty],
+                    vec![Pipe::Empty, Pipe::NorthEast, Pipe::Horizontal, Pipe::NorthWest, Pipe::Empty],
+                    vec![Pipe::Empty, Pipe::Empty, Pipe::Empty, Pipe::Empty, Pipe::Empty],
+                ],
+                Pos { x: 1, y: 1 },
+            ))
+        );
+    }
+
+    #[test]
+    fn infer_start_pipe_test() {
+        let infer = |input: &str| parse_grid(input).and_then(|(pipes, pos)| infer_start_pipe(&pipes, pos));
+        assert_eq!(infer(".|.\n-S-\n.|."), Err(ParseError::StartConnections {
+            pos: Pos { x: 1, y: 1 },
+            connections: vec![NORTH, SOUTH, EAST, WEST],
+        }));
+        assert_eq!(infer("F7.\n|S-\n.L."), Err(ParseError::StartConnections {
+            pos: Pos { x: 1, y: 1 },
+            connections: vec![NORTH, SOUTH, EAST],
+        }));
+        assert_eq!(infer("...\n.S-\n..."), Err(ParseError::StartConnections {
+            pos: Pos { x: 1, y: 1 },
+            connections: vec![EAST],
+        }));
+        assert_eq!(infer("...\n.S.\n..."), Err(ParseError::StartConnections {
+            pos: Pos { x: 1, y: 1 },
+            connections: vec![],
+        }));
+        assert_eq!(infer(".|.\n.S.\n.|."), Ok(Pipe::Vertical));
+        assert_eq!(infer("...\n-S-\n..."), Ok(Pipe::Horizontal));
+        assert_eq!(infer(".|.\n.S-\n..."), Ok(Pipe::NorthEast));
+        assert_eq!(infer(".|.\n-S.\n..."), Ok(Pipe::NorthWest));
+        assert_eq!(infer("...\n.S-\n.|."), Ok(Pipe::SouthEast));
+        assert_eq!(infer("...\n-S.\n.|."), Ok(Pipe::SouthWest));
+        // Pipes that are next to the start but point elsewhere don't count
+        assert_eq!(infer(".-.\n|S|\n.-."), Err(ParseError::StartConnections {
+            pos: Pos { x: 1, y: 1 },
+            connections: vec![],
+        }));
+        assert_eq!(infer("S.\n.."), Err(ParseError::StartConnections {
+            pos: Pos { x: 0, y: 0 },
+            connections: vec![],
+        }));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        assert_eq!(parse_pipes("..\n.."), Err(ParseError::NoStart));
+        assert_eq!(parse_pipes(".S\nSx"), Err(ParseError::MultipleStarts {
+            first: Pos { x: 1, y: 0 },
+            second: Pos { x: 0, y: 1 },
+        }));
+        assert_eq!(parse_pipes("S.\n.x"), Err(ParseError::UnknownCharacter {
+            c: 'x',
+            pos: Pos { x: 1, y: 1 },
+        }));
+        assert_eq!(
+            parse_pipes(".|.\n-S-\n.|.").unwrap_err().to_string(),
+            "start tile at (1, 1) has 4 neighbours connecting back to it [north, south, east, west], expected exactly 2"
+        );
+        assert_eq!(parse_pipes("S7\nL"), Err(ParseError::RaggedRow { y: 1, length: 1, expected: 2 }));
+        assert_eq!(parse_pipes("S7\nLJ\n..."), Err(ParseError::RaggedRow { y: 2, length: 3, expected: 2 }));
+        assert_eq!(parse_pipes("S7\nLJ"), Ok((vec![
+            vec![Pipe::SouthEast, Pipe::SouthWest],
+            vec![Pipe::NorthEast, Pipe::NorthWest],
+        ], Pos { x: 0, y: 0 })));
+    }
+
+    #[test]
+    fn open_loop_test() {
+        // The pipes next to the start lead off the grid, into nothing or into
+        // pipes that don't connect back
+        assert_eq!(parse_pipes(".|.\n.S.\n.|."), Err(ParseError::OpenLoop { at: Pos { x: 1, y: 2 } }));
+        assert_eq!(parse_pipes(".|.\n.S.\n.|.\n.|."), Err(ParseError::OpenLoop { at: Pos { x: 1, y: 3 } }));
+        assert_eq!(parse_pipes("...\n.S7\n.||\n.L.\n..."), Err(ParseError::OpenLoop { at: Pos { x: 1, y: 3 } }));
+        assert_eq!(parse_pipes("....\n.S7.\n.||.\n.L-.\n...."), Err(ParseError::OpenLoop { at: Pos { x: 2, y: 3 } }));
+        assert_eq!(parse_pipes(".S7\n.|L\n.L-"), Err(ParseError::OpenLoop { at: Pos { x: 2, y: 2 } }));
+        assert_eq!(
+            Day10::parse("F-.\nSJ.\n...").unwrap_err(),
+            aoc::ParseError::Invalid("the loop from the start tile breaks off at (1, 1)".to_string())
+        );
+    }
+
+    #[test]
+    fn visit_pipes_test1() {
+        let (pipes, pos) = parse_pipes(".....
+.S-7.
+.|.|.
+.L-J.
+.....").unwrap();
+        let vertices = visit_pipes(&pipes, pos);
+        assert_eq!(
+            vertices,
+            vec![
+                Pos { x: 1, y: 1 },
+                Pos { x: 1, y: 2 },
+                Pos { x: 1, y: 3 },
+                Pos { x: 2, y: 3 },
+                Pos { x: 3, y: 3 },
+                Pos { x: 3, y: 2 },
+                Pos { x: 3, y: 1 },
+                Pos { x: 2, y: 1 },
+            ]
+        );
+        assert_eq!(double_area(&vertices), 8);
+    }
+
+    #[test]
+    fn visit_pipes_test_simpler() {
+        let (pipes, pos) = parse_pipes(INPUT_SIMPLER).unwrap();
+        let vertices = visit_pipes(&pipes, pos);
+        assert_eq!(vertices.len(), 140);
+        assert_eq!(vertices[0], Pos { x: 12, y: 4 });
+    }
+
+    #[test]
+    fn enclosed_test1() {
+        enclosure_test(
+            "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........",
+            4,
+        );
+    }
+
+    #[test]
+    fn enclosed_test_squeeze() {
+        enclosure_test(INPUT_SQUEEZE, 4);
+    }
+
+    #[test]
+    fn enclosed_test_simpler() {
+        enclosure_test(INPUT_SIMPLER, 8);
+    }
+
+    #[test]
+    fn enclosed_test_junk() {
+        enclosure_test(INPUT_JUNK, 10);
+    }
+
+    #[test]
+    fn distance_grid_test1() {
+        let (pipes, pos) = parse_pipes(".....
+.S-7.
+.|.|.
+.L-J.
+.....").unwrap();
+        let distances = calculate_distances(&pipes, pos);
+        assert_eq!(
+            distances,
+            (
+                4,
+                vec![
+                    vec![-1, -1, -1, -1, -1],
+                    vec![-1,  0,  1,  2, -1],
+                    vec![-1,  1, -1,  3, -1],
+                    vec![-1,  2,  3,  4, -1],
+                    vec![-1, -1, -1, -1, -1],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            Day10::parse("...\n.F.").unwrap_err(),
+            aoc::ParseError::Invalid("no start tile S in the input".to_string())
+        );
+    }
+}
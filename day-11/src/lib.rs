@@ -0,0 +1,651 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashSet};
+use std::num::NonZeroU64;
+
+use aoc::{Answer, ParseError, Solution};
+
+pub fn parse_input(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+pub fn find_empty_rows_and_cols(input: &[Vec<char>]) -> (Vec<usize>, Vec<usize>) {
+    let empty_rows = (0..input.len())
+        .filter(|row| input[*row].iter().all(|c| *c != '#'))
+        .collect();
+    let empty_cols = (0..input.first().map_or(0, |row| row.len()))
+        .filter(|col| input.iter().all(|row| row[*col] != '#'))
+        .collect();
+
+    (empty_rows, empty_cols)
+}
+
+// Galaxies are (row, col)
+pub fn find_galaxies(input: &[Vec<char>]) -> Vec<(usize, usize)> {
+    let mut galaxies: Vec<(usize, usize)> = Vec::new();
+
+    for (row, line) in input.iter().enumerate() {
+        for (col, c) in line.iter().enumerate() {
+            if *c == '#' {
+                galaxies.push((row, col));
+            }
+        }
+    }
+
+    galaxies
+}
+
+// Maps every index along an axis of length `len` to where it ends up once each
+// empty line has been replaced by `factor` lines. There's no replacing a line
+// by none, hence the NonZeroU64.
+pub fn expand_axis(len: usize, empty: &[usize], factor: NonZeroU64) -> Vec<u64> {
+    let mut is_empty = vec![false; len];
+    for index in empty {
+        is_empty[*index] = true;
+    }
+
+    let mut expanded = Vec::with_capacity(len);
+    let mut empty_before = 0;
+    for (index, empty) in is_empty.iter().enumerate() {
+        expanded.push(index as u64 + empty_before * (factor.get() - 1));
+        if *empty {
+            empty_before += 1;
+        }
+    }
+
+    expanded
+}
+
+pub fn expand_galaxies(
+    galaxies: &[(usize, usize)],
+    size: (usize, usize),
+    empties: &(Vec<usize>, Vec<usize>),
+    factor: NonZeroU64,
+) -> Vec<(u64, u64)> {
+    let rows = expand_axis(size.0, &empties.0, factor);
+    let cols = expand_axis(size.1, &empties.1, factor);
+
+    galaxies.iter().map(|(row, col)| (rows[*row], cols[*col])).collect()
+}
+
+// Sum of |a - b| over all pairs. Once sorted, the i:th value is the larger one
+// in exactly i pairs, so it contributes value * i minus everything before it.
+pub fn sum_axis_distances(mut values: Vec<u64>) -> u128 {
+    values.sort_unstable();
+
+    let mut sum: u128 = 0;
+    let mut prefix: u128 = 0;
+    for (i, value) in values.iter().enumerate() {
+        sum += *value as u128 * i as u128 - prefix;
+        prefix += *value as u128;
+    }
+
+    sum
+}
+
+pub fn sum_distances(galaxies: &[(u64, u64)]) -> u128 {
+    sum_axis_distances(galaxies.iter().map(|g| g.0).collect())
+        + sum_axis_distances(galaxies.iter().map(|g| g.1).collect())
+}
+
+pub fn solve(input: &str, factor: NonZeroU64) -> u128 {
+    Universe::parse(input, factor).sum_distances()
+}
+
+// A pair of galaxies, by index into Universe::galaxies(), and their distance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair {
+    pub a: usize,
+    pub b: usize,
+    pub distance: u64,
+}
+impl Pair {
+    fn new(a: usize, b: usize, distance: u64) -> Self {
+        Self { a: a.min(b), b: a.max(b), distance }
+    }
+}
+
+// The expanded universe. Manhattan distance between two galaxies is the same
+// as the Chebyshev distance between them after rotating the grid 45 degrees,
+// (x, y) -> (x + y, x - y), which turns "within distance d" into a square
+// that can be swept along one axis.
+#[derive(Debug, Clone)]
+pub struct Universe {
+    galaxies: Vec<(u64, u64)>,
+    rotated: Vec<(i64, i64)>,
+}
+impl Universe {
+    pub fn new(data: &[Vec<char>], factor: NonZeroU64) -> Self {
+        let empties = find_empty_rows_and_cols(data);
+        let galaxies = find_galaxies(data);
+        let size = (data.len(), data.first().map_or(0, |row| row.len()));
+        Self::from_galaxies(expand_galaxies(&galaxies, size, &empties, factor))
+    }
+
+    pub fn parse(input: &str, factor: NonZeroU64) -> Self {
+        Self::new(&parse_input(input), factor)
+    }
+
+    pub fn from_galaxies(galaxies: Vec<(u64, u64)>) -> Self {
+        let rotated = galaxies
+            .iter()
+            .map(|(row, col)| ((row + col) as i64, *row as i64 - *col as i64))
+            .collect();
+        Self { galaxies, rotated }
+    }
+
+    pub fn galaxies(&self) -> &[(u64, u64)] {
+        &self.galaxies
+    }
+
+    pub fn distance(&self, a: usize, b: usize) -> u64 {
+        let (a, b) = (self.rotated[a], self.rotated[b]);
+        a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+    }
+
+    fn num_pairs(&self) -> usize {
+        self.galaxies.len() * self.galaxies.len().saturating_sub(1) / 2
+    }
+
+    pub fn sum_distances(&self) -> u128 {
+        sum_distances(&self.galaxies)
+    }
+
+    pub fn nearest_neighbour(&self, galaxy: usize) -> Option<Pair> {
+        (0..self.galaxies.len())
+            .filter(|other| *other != galaxy)
+            .map(|other| Pair::new(galaxy, other, self.distance(galaxy, other)))
+            .min_by_key(|pair| (pair.distance, pair.a, pair.b))
+    }
+
+    // Galaxy indices sorted along the first rotated axis
+    fn sorted_by_u(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.rotated.len()).collect();
+        order.sort_unstable_by_key(|i| self.rotated[*i]);
+        order
+    }
+
+    // Number of pairs no further apart than max_distance, sweeping along u
+    // and counting the v coordinates inside the window with a Fenwick tree
+    fn count_pairs_within(&self, order: &[usize], vs: &[i64], max_distance: u64) -> usize {
+        let d = max_distance as i64;
+        let mut tree = Fenwick::new(vs.len());
+        let mut count = 0;
+        let mut first = 0;
+        for index in order {
+            let (u, v) = self.rotated[*index];
+            while self.rotated[order[first]].0 < u - d {
+                let old_v = self.rotated[order[first]].1;
+                tree.add(vs.partition_point(|x| *x < old_v), -1);
+                first += 1;
+            }
+            let low = vs.partition_point(|x| *x < v - d);
+            let high = vs.partition_point(|x| *x <= v + d);
+            count += (tree.sum(high) - tree.sum(low)) as usize;
+            tree.add(vs.partition_point(|x| *x < v), 1);
+        }
+
+        count
+    }
+
+    // Calls visit for every pair no further apart than max_distance until it
+    // returns false
+    fn visit_pairs_within(&self, order: &[usize], max_distance: u64, mut visit: impl FnMut(Pair) -> bool) {
+        let d = max_distance as i64;
+        let mut window: BTreeSet<(i64, usize)> = BTreeSet::new();
+        let mut first = 0;
+        for index in order {
+            let (u, v) = self.rotated[*index];
+            while self.rotated[order[first]].0 < u - d {
+                window.remove(&(self.rotated[order[first]].1, order[first]));
+                first += 1;
+            }
+            for (_, other) in window.range((v - d, 0)..=(v + d, usize::MAX)) {
+                if !visit(Pair::new(*index, *other, self.distance(*index, *other))) {
+                    return;
+                }
+            }
+            window.insert((v, *index));
+        }
+    }
+
+    // The k pairs with the shortest distances, closest first. Ties at the
+    // k:th distance are broken arbitrarily.
+    pub fn closest_pairs(&self, k: usize) -> Vec<Pair> {
+        let k = k.min(self.num_pairs());
+        if k == 0 {
+            return Vec::new();
+        }
+        let order = self.sorted_by_u();
+        // Distinct v coordinates, for indexing the Fenwick tree
+        let mut vs: Vec<i64> = self.rotated.iter().map(|p| p.1).collect();
+        vs.sort_unstable();
+        vs.dedup();
+
+        // Smallest distance that at least k pairs are within
+        let (mut low, mut high) = (0, self.max_distance());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.count_pairs_within(&order, &vs, mid) >= k {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        // Fewer than k pairs are strictly closer, so take all of those and
+        // fill up with pairs at exactly the limit
+        let mut pairs = Vec::new();
+        if low > 0 {
+            self.visit_pairs_within(&order, low - 1, |pair| {
+                pairs.push(pair);
+                true
+            });
+        }
+        let mut missing = k - pairs.len();
+        self.visit_pairs_within(&order, low, |pair| {
+            if pair.distance == low {
+                pairs.push(pair);
+                missing -= 1;
+            }
+            missing > 0
+        });
+
+        pairs.sort_unstable_by_key(|pair| (pair.distance, pair.a, pair.b));
+        pairs
+    }
+
+    fn max_distance(&self) -> u64 {
+        let span = |values: Vec<i64>| match (values.iter().min(), values.iter().max()) {
+            (Some(min), Some(max)) => max.abs_diff(*min),
+            _ => 0,
+        };
+        span(self.rotated.iter().map(|p| p.0).collect()).max(span(self.rotated.iter().map(|p| p.1).collect()))
+    }
+
+    // The k pairs with the longest distances, farthest first. Ties at the
+    // k:th distance are broken arbitrarily.
+    pub fn farthest_pairs(&self, k: usize) -> Vec<Pair> {
+        let k = k.min(self.num_pairs());
+        if k == 0 {
+            return Vec::new();
+        }
+
+        // Along each rotated axis the pairs can be listed from the largest
+        // difference down. A pair's distance is at least its difference on
+        // either axis, and no pair that hasn't been listed yet can be further
+        // apart than the larger of the two next differences.
+        let mut axes = [
+            SpreadPairs::new(self.rotated.iter().map(|p| p.0).collect()),
+            SpreadPairs::new(self.rotated.iter().map(|p| p.1).collect()),
+        ];
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+        let mut best: BinaryHeap<Reverse<u64>> = BinaryHeap::new();
+        let mut pairs = Vec::new();
+
+        loop {
+            let axis = match (axes[0].peek(), axes[1].peek()) {
+                (None, None) => break,
+                (Some(_), None) => 0,
+                (None, Some(_)) => 1,
+                (Some(u), Some(v)) => if u >= v { 0 } else { 1 },
+            };
+            let bound = axes[axis].peek().unwrap();
+            if best.len() >= k && best.peek().unwrap().0 >= bound {
+                break;
+            }
+
+            let (a, b) = axes[axis].pop().unwrap();
+            let pair = Pair::new(a, b, self.distance(a, b));
+            if seen.insert((pair.a, pair.b)) {
+                pairs.push(pair);
+                best.push(Reverse(pair.distance));
+                if best.len() > k {
+                    best.pop();
+                }
+            }
+        }
+
+        pairs.sort_unstable_by_key(|pair| (Reverse(pair.distance), pair.a, pair.b));
+        pairs.truncate(k);
+        pairs
+    }
+
+    // Number of pairs for each distance, grouped into buckets of the given
+    // width and keyed by the smallest distance in the bucket. Unlike the
+    // queries above, every pair has to be looked at, so this takes O(n^2)
+    // time in the number of galaxies.
+    pub fn distance_histogram(&self, bucket_width: NonZeroU64) -> BTreeMap<u64, usize> {
+        let bucket_width = bucket_width.get();
+        let mut histogram = BTreeMap::new();
+        for a in 0..self.galaxies.len() {
+            for b in a + 1..self.galaxies.len() {
+                let bucket = self.distance(a, b) / bucket_width * bucket_width;
+                *histogram.entry(bucket).or_insert(0) += 1;
+            }
+        }
+        histogram
+    }
+}
+
+// Pairs of values in decreasing order of their difference. With the values
+// sorted, the largest difference is first against last, and every other pair
+// (i, j) is only ever smaller than (i - 1, j) and (i, j + 1).
+struct SpreadPairs {
+    values: Vec<i64>,
+    order: Vec<usize>,
+    heap: BinaryHeap<(u64, usize, usize)>,
+    queued: HashSet<(usize, usize)>,
+}
+impl SpreadPairs {
+    fn new(values: Vec<i64>) -> Self {
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_unstable_by_key(|i| values[*i]);
+        let mut spread = Self {
+            values,
+            order,
+            heap: BinaryHeap::new(),
+            queued: HashSet::new(),
+        };
+        if spread.order.len() > 1 {
+            spread.push(0, spread.order.len() - 1);
+        }
+        spread
+    }
+
+    fn push(&mut self, i: usize, j: usize) {
+        if i < j && self.queued.insert((i, j)) {
+            let diff = self.values[self.order[j]].abs_diff(self.values[self.order[i]]);
+            self.heap.push((diff, i, j));
+        }
+    }
+
+    fn peek(&self) -> Option<u64> {
+        self.heap.peek().map(|(diff, _, _)| *diff)
+    }
+
+    fn pop(&mut self) -> Option<(usize, usize)> {
+        let (_, i, j) = self.heap.pop()?;
+        self.push(i + 1, j);
+        if j > 0 {
+            self.push(i, j - 1);
+        }
+        Some((self.order[i], self.order[j]))
+    }
+}
+
+struct Fenwick {
+    tree: Vec<i64>,
+}
+impl Fenwick {
+    fn new(len: usize) -> Self {
+        Self { tree: vec![0; len + 1] }
+    }
+
+    fn add(&mut self, index: usize, value: i64) {
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] += value;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // Sum of the first len entries
+    fn sum(&self, len: usize) -> i64 {
+        let mut sum = 0;
+        let mut i = len;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+}
+
+// How many lines each empty one becomes in the two parts
+const YOUNG: NonZeroU64 = NonZeroU64::new(2).unwrap();
+const OLD: NonZeroU64 = NonZeroU64::new(1_000_000).unwrap();
+
+pub struct Day11;
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Parsed = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let image = parse_input(input);
+        for (row, line) in image.iter().enumerate() {
+            if line.len() != image[0].len() {
+                return Err(ParseError::Syntax { line: row + 1, message: "Rows differ in length".to_string() });
+            }
+            if let Some(c) = line.iter().find(|c| **c != '.' && **c != '#') {
+                return Err(ParseError::Syntax { line: row + 1, message: format!("Unknown character: {}", c) });
+            }
+        }
+        Ok(image)
+    }
+
+    fn part1(image: &Self::Parsed) -> Answer {
+        Universe::new(image, YOUNG).sum_distances().into()
+    }
+
+    fn part2(image: &Self::Parsed) -> Answer {
+        Universe::new(image, OLD).sum_distances().into()
+    }
+}
+
+aoc::register!(Day11);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn times(factor: u64) -> NonZeroU64 {
+        NonZeroU64::new(factor).unwrap()
+    }
+
+    const INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn empty_test() {
+        let data = parse_input(INPUT);
+        let empties = find_empty_rows_and_cols(&data);
+        assert_eq!(empties, (vec![3, 7], vec![2, 5, 8]));
+
+        assert_eq!(find_empty_rows_and_cols(&[]), (vec![], vec![]));
+        assert_eq!(Universe::parse("", times(2)).sum_distances(), 0);
+    }
+
+    #[test]
+    fn find_galaxies_test() {
+        let data = parse_input(INPUT);
+        let galaxies = find_galaxies(&data);
+
+        assert_eq!(galaxies.len(), 9);
+        assert_eq!(galaxies[0], (0, 3));
+        assert_eq!(galaxies[1], (1, 7));
+        assert_eq!(galaxies[2], (2, 0));
+        assert_eq!(galaxies[3], (4, 6));
+        assert_eq!(galaxies[4], (5, 1));
+        assert_eq!(galaxies[5], (6, 9));
+        assert_eq!(galaxies[6], (8, 7));
+        assert_eq!(galaxies[7], (9, 0));
+        assert_eq!(galaxies[8], (9, 4));
+    }
+
+    #[test]
+    fn expand_axis_test() {
+        assert_eq!(expand_axis(10, &[3, 7], times(2)), vec![0, 1, 2, 3, 5, 6, 7, 8, 10, 11]);
+        assert_eq!(expand_axis(5, &[0, 1], times(10)), vec![0, 10, 20, 21, 22]);
+        assert_eq!(expand_axis(3, &[], times(10)), vec![0, 1, 2]);
+        // A factor of 1 leaves everything where it was
+        assert_eq!(expand_axis(3, &[1], times(1)), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn expand_galaxies_test() {
+        // The same galaxies as when the grid itself is doubled up
+        let data = parse_input(INPUT);
+        let empties = find_empty_rows_and_cols(&data);
+        let galaxies = find_galaxies(&data);
+        let expanded = expand_galaxies(&galaxies, (10, 10), &empties, times(2));
+
+        assert_eq!(
+            expanded,
+            vec![(0, 4), (1, 9), (2, 0), (5, 8), (6, 1), (7, 12), (10, 9), (11, 0), (11, 5)]
+        );
+    }
+
+    #[test]
+    fn sum_axis_distances_test() {
+        assert_eq!(sum_axis_distances(vec![]), 0);
+        assert_eq!(sum_axis_distances(vec![5]), 0);
+        assert_eq!(sum_axis_distances(vec![7, 1, 4]), 3 + 6 + 3);
+        assert_eq!(sum_axis_distances(vec![2, 2, 2]), 0);
+    }
+
+    #[test]
+    fn sum_distances_test() {
+        assert_eq!(solve(INPUT, times(2)), 374);
+        assert_eq!(solve(INPUT, times(10)), 1030);
+        assert_eq!(solve(INPUT, times(100)), 8410);
+    }
+
+    // Every pair sorted by distance, for checking the faster queries against
+    fn all_pairs(universe: &Universe) -> Vec<Pair> {
+        let n = universe.galaxies().len();
+        let mut pairs = Vec::new();
+        for a in 0..n {
+            for b in a + 1..n {
+                pairs.push(Pair::new(a, b, universe.distance(a, b)));
+            }
+        }
+        pairs.sort_unstable_by_key(|pair| pair.distance);
+        pairs
+    }
+
+    fn distances(pairs: &[Pair]) -> Vec<u64> {
+        pairs.iter().map(|pair| pair.distance).collect()
+    }
+
+    // Deterministic scattered galaxies, with plenty of equal distances
+    fn scattered(n: usize, size: u64) -> Universe {
+        let mut state: u64 = 11;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % size
+        };
+        Universe::from_galaxies((0..n).map(|_| (next(), next())).collect())
+    }
+
+    #[test]
+    fn distance_test() {
+        let universe = Universe::parse(INPUT, times(2));
+        // Galaxies 5 and 9 in the puzzle text are indices 4 and 8 here
+        assert_eq!(universe.distance(4, 8), 9);
+        assert_eq!(universe.distance(0, 6), 15);
+        assert_eq!(universe.distance(2, 5), 17);
+        assert_eq!(universe.distance(7, 8), 5);
+        assert_eq!(universe.sum_distances(), 374);
+    }
+
+    #[test]
+    fn nearest_neighbour_test() {
+        let universe = Universe::parse(INPUT, times(2));
+        assert_eq!(universe.nearest_neighbour(7), Some(Pair { a: 7, b: 8, distance: 5 }));
+        assert_eq!(universe.nearest_neighbour(0), Some(Pair { a: 0, b: 1, distance: 6 }));
+
+        let universe = Universe::parse("#..", times(2));
+        assert_eq!(universe.nearest_neighbour(0), None);
+    }
+
+    #[test]
+    fn closest_pairs_test() {
+        let universe = Universe::parse(INPUT, times(2));
+        assert_eq!(distances(&universe.closest_pairs(4)), vec![5, 5, 5, 5]);
+        assert_eq!(distances(&universe.closest_pairs(5)), vec![5, 5, 5, 5, 6]);
+
+        for factor in [2, 10, 1_000_000] {
+            let universe = Universe::parse(INPUT, times(factor));
+            let expected = all_pairs(&universe);
+            for k in [0, 1, 5, 17, 36, 100] {
+                let closest = universe.closest_pairs(k);
+                assert_eq!(distances(&closest), distances(&expected[..k.min(36)]));
+            }
+        }
+    }
+
+    #[test]
+    fn farthest_pairs_test() {
+        let universe = Universe::parse(INPUT, times(2));
+        let farthest = universe.farthest_pairs(1);
+        assert_eq!(farthest, vec![Pair { a: 1, b: 7, distance: 19 }]);
+
+        for factor in [2, 10, 1_000_000] {
+            let universe = Universe::parse(INPUT, times(factor));
+            let mut expected = all_pairs(&universe);
+            expected.reverse();
+            for k in [0, 1, 5, 17, 36, 100] {
+                let farthest = universe.farthest_pairs(k);
+                assert_eq!(distances(&farthest), distances(&expected[..k.min(36)]));
+            }
+        }
+    }
+
+    #[test]
+    fn pair_queries_match_brute_force_test() {
+        let universe = scattered(300, 50);
+        let expected = all_pairs(&universe);
+        let mut reversed = expected.clone();
+        reversed.reverse();
+        for k in [1, 10, 100, 1000, 5000] {
+            assert_eq!(distances(&universe.closest_pairs(k)), distances(&expected[..k]));
+            assert_eq!(distances(&universe.farthest_pairs(k)), distances(&reversed[..k]));
+        }
+        for pair in universe.closest_pairs(1000) {
+            assert_eq!(pair.distance, universe.distance(pair.a, pair.b));
+        }
+    }
+
+    #[test]
+    fn distance_histogram_test() {
+        let universe = Universe::parse(INPUT, times(2));
+        let histogram = universe.distance_histogram(times(1));
+        assert_eq!(histogram.values().sum::<usize>(), 36);
+        assert_eq!(histogram[&5], 4);
+        assert_eq!(histogram[&9], 7);
+        assert_eq!(histogram.keys().last(), Some(&19));
+
+        let histogram = universe.distance_histogram(times(10));
+        let expected = all_pairs(&universe);
+        assert_eq!(histogram[&0], expected.iter().filter(|pair| pair.distance < 10).count());
+        assert_eq!(histogram[&10], expected.iter().filter(|pair| pair.distance >= 10).count());
+    }
+
+    #[test]
+    fn many_galaxies_test() {
+        // A diagonal of 10^5 galaxies with an empty line between each
+        let galaxies: Vec<(u64, u64)> = (0..100_000).map(|i| (i * 3, i * 5)).collect();
+        let n: u128 = 100_000;
+        // Sum of (j - i) over all pairs i < j is (n + 1) * n * (n - 1) / 6
+        let pairs = (n + 1) * n * (n - 1) / 6;
+        assert_eq!(sum_distances(&galaxies), pairs * 8);
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(Day11::parse("#.\n.#").unwrap(), vec![vec!['#', '.'], vec!['.', '#']]);
+        assert_eq!(
+            Day11::parse("#.\n.").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Rows differ in length".to_string() }
+        );
+        assert!(Day11::parse("#.\n.x").is_err());
+    }
+}
@@ -0,0 +1,458 @@
+use aoc::{Answer, ParseError, Solution};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+pub fn parse_springs(springs: &str) -> Vec<Spring> {
+    springs
+        .chars()
+        .map(|c| match c {
+            '.' => Spring::Operational,
+            '#' => Spring::Damaged,
+            '?' => Spring::Unknown,
+            _ => panic!("Invalid spring character"),
+        })
+        .collect()
+}
+
+pub fn springs_to_string(springs: &[Spring]) -> String {
+    springs
+        .iter()
+        .map(|s| match s {
+            Spring::Operational => '.',
+            Spring::Damaged => '#',
+            Spring::Unknown => '?',
+        })
+        .collect()
+}
+
+pub fn parse_input(input: &str) -> Vec<(Vec<Spring>, Vec<usize>)> {
+    let mut result = Vec::new();
+
+    for line in input.lines() {
+        let (spring_str, groups_str) = line.split_once(' ').unwrap_or((line.trim(), ""));
+
+        let groups: Vec<usize> = if groups_str.is_empty() {
+            Vec::new()
+        } else {
+            groups_str.split(',').map(|s| s.parse::<usize>().unwrap()).collect()
+        };
+
+        result.push((parse_springs(spring_str), groups));
+    }
+
+    result
+}
+
+// The pattern and groups repeated five times, with an unknown spring between
+// each copy of the pattern
+pub fn unfold(pattern: &[Spring], groups: &[usize]) -> (Vec<Spring>, Vec<usize>) {
+    let mut unfolded = pattern.to_vec();
+    for _ in 1..5 {
+        unfolded.push(Spring::Unknown);
+        unfolded.extend_from_slice(pattern);
+    }
+    (unfolded, groups.repeat(5))
+}
+
+pub fn matches_pattern(springs: &[Spring], pattern: &[Spring]) -> bool {
+    springs.len() == pattern.len()
+        && springs
+            .iter()
+            .zip(pattern.iter())
+            .all(|(spring, p)| *p == Spring::Unknown || spring == p)
+}
+
+// For every position in the pattern and every group, the number of ways the
+// rest of the pattern can hold the remaining groups, given that the spring
+// before the position is operational (or that it's the very start).
+struct Table<'a> {
+    pattern: &'a [Spring],
+    groups: &'a [usize],
+    // operational[i] is the number of operational springs before position i,
+    // same for damaged
+    operational: Vec<usize>,
+    damaged: Vec<usize>,
+    counts: Vec<Vec<u64>>,
+}
+impl<'a> Table<'a> {
+    fn new(pattern: &'a [Spring], groups: &'a [usize]) -> Self {
+        let n = pattern.len();
+        let mut operational = vec![0; n + 1];
+        let mut damaged = vec![0; n + 1];
+        for (i, spring) in pattern.iter().enumerate() {
+            operational[i + 1] = operational[i] + (*spring == Spring::Operational) as usize;
+            damaged[i + 1] = damaged[i] + (*spring == Spring::Damaged) as usize;
+        }
+
+        let mut table = Self {
+            pattern,
+            groups,
+            operational,
+            damaged,
+            counts: vec![vec![0; groups.len() + 1]; n + 1],
+        };
+
+        table.counts[n][groups.len()] = 1;
+        for i in (0..n).rev() {
+            for g in 0..=groups.len() {
+                // Either this spring is operational and the groups start later...
+                let mut count = 0;
+                if pattern[i] != Spring::Damaged {
+                    count += table.counts[i + 1][g];
+                }
+                // ...or the next group starts right here
+                if let Some(next) = table.place(i, g) {
+                    count += table.counts[next][g + 1];
+                }
+                table.counts[i][g] = count;
+            }
+        }
+
+        table
+    }
+
+    // Where the next free position is if group g starts at position start,
+    // provided the group fits there
+    fn place(&self, start: usize, g: usize) -> Option<usize> {
+        let len = *self.groups.get(g)?;
+        let end = start + len;
+        if end > self.pattern.len() || self.operational[end] != self.operational[start] {
+            return None;
+        }
+        if end == self.pattern.len() {
+            return Some(end);
+        }
+        if self.pattern[end] == Spring::Damaged {
+            return None;
+        }
+        Some(end + 1)
+    }
+
+    // First position from `from` onwards where group g can start, and still
+    // leave room for the remaining groups, when `free` is the first position
+    // after the previous group
+    fn find_start(&self, free: usize, from: usize, g: usize) -> Option<usize> {
+        for start in from..self.pattern.len() {
+            // Everything skipped over has to be operational
+            if self.damaged[start] != self.damaged[free] {
+                return None;
+            }
+            if let Some(next) = self.place(start, g) {
+                if self.counts[next][g + 1] > 0 {
+                    return Some(start);
+                }
+            }
+        }
+        None
+    }
+}
+
+// Counts the arrangements without listing them, in O(springs * groups)
+pub fn count_arrangements(pattern: &[Spring], groups: &[usize]) -> u64 {
+    Table::new(pattern, groups).counts[0][0]
+}
+
+// Lazily lists every arrangement consistent with the pattern, in order of
+// where the groups start. The table makes sure that no branch is followed
+// unless it ends up in at least one arrangement.
+pub struct Arrangements<'a> {
+    table: Table<'a>,
+    // Start position of each group in the current arrangement
+    starts: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+impl<'a> Arrangements<'a> {
+    pub fn new(pattern: &'a [Spring], groups: &'a [usize]) -> Self {
+        let table = Table::new(pattern, groups);
+        let done = table.counts[0][0] == 0;
+        Self {
+            table,
+            starts: Vec::new(),
+            started: false,
+            done,
+        }
+    }
+
+    fn free_after(&self, g: usize) -> usize {
+        match g {
+            0 => 0,
+            _ => self.table.place(self.starts[g - 1], g - 1).unwrap(),
+        }
+    }
+
+    // Places the remaining groups as early as possible
+    fn fill(&mut self) {
+        for g in self.starts.len()..self.table.groups.len() {
+            let free = self.free_after(g);
+            let start = self.table.find_start(free, free, g).unwrap();
+            self.starts.push(start);
+        }
+    }
+
+    // Moves the last group that can be moved one step on, and drops the ones
+    // after it
+    fn advance(&mut self) -> bool {
+        while let Some(start) = self.starts.pop() {
+            let g = self.starts.len();
+            let free = self.free_after(g);
+            if let Some(next) = self.table.find_start(free, start + 1, g) {
+                self.starts.push(next);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn current(&self) -> Vec<Spring> {
+        let mut springs = vec![Spring::Operational; self.table.pattern.len()];
+        for (start, len) in self.starts.iter().zip(self.table.groups.iter()) {
+            for spring in springs[*start..start + len].iter_mut() {
+                *spring = Spring::Damaged;
+            }
+        }
+        springs
+    }
+}
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<Spring>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.started && !self.advance() {
+            self.done = true;
+            return None;
+        }
+        self.started = true;
+        self.fill();
+        Some(self.current())
+    }
+}
+
+pub fn arrangements<'a>(pattern: &'a [Spring], groups: &'a [usize]) -> Arrangements<'a> {
+    Arrangements::new(pattern, groups)
+}
+
+pub struct Day12;
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Parsed = Vec<(Vec<Spring>, Vec<usize>)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        // parse_input panics on bad input, so check every line first
+        for (i, line) in input.lines().enumerate() {
+            let syntax = |message: String| ParseError::Syntax { line: i + 1, message };
+            let (spring_str, groups_str) = line.split_once(' ').unwrap_or((line.trim(), ""));
+            if let Some(c) = spring_str.chars().find(|c| !".#?".contains(*c)) {
+                return Err(syntax(format!("Invalid spring: {}", c)));
+            }
+            if !groups_str.is_empty() && groups_str.split(',').any(|group| group.parse::<usize>().is_err()) {
+                return Err(syntax(format!("Invalid groups: {}", groups_str)));
+            }
+            // A group is a run of damaged springs, so there's no such thing
+            // as an empty one
+            if !groups_str.is_empty() && groups_str.split(',').any(|group| group.parse::<usize>() == Ok(0)) {
+                return Err(syntax(format!("Empty group: {}", groups_str)));
+            }
+        }
+        Ok(parse_input(input))
+    }
+
+    fn part1(records: &Self::Parsed) -> Answer {
+        records
+            .iter()
+            .map(|(pattern, groups)| count_arrangements(pattern, groups))
+            .sum::<u64>()
+            .into()
+    }
+
+    fn part2(records: &Self::Parsed) -> Answer {
+        records
+            .iter()
+            .map(|(pattern, groups)| {
+                let (pattern, groups) = unfold(pattern, groups);
+                count_arrangements(&pattern, &groups)
+            })
+            .sum::<u64>()
+            .into()
+    }
+}
+
+aoc::register!(Day12);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    fn listed(pattern: &str, groups: &[usize]) -> Vec<String> {
+        let pattern = parse_springs(pattern);
+        arrangements(&pattern, groups).map(|springs| springs_to_string(&springs)).collect()
+    }
+
+    // The lengths of the runs of damaged springs
+    fn groups_of(springs: &[Spring]) -> Vec<usize> {
+        springs
+            .split(|spring| *spring != Spring::Damaged)
+            .map(|run| run.len())
+            .filter(|len| *len > 0)
+            .collect()
+    }
+
+    // Tries every way of filling in the unknown springs
+    fn brute_force(pattern: &[Spring], groups: &[usize]) -> u64 {
+        let unknowns: Vec<usize> = (0..pattern.len()).filter(|i| pattern[*i] == Spring::Unknown).collect();
+        let mut count = 0;
+        for mask in 0..1u32 << unknowns.len() {
+            let mut springs = pattern.to_vec();
+            for (bit, i) in unknowns.iter().enumerate() {
+                springs[*i] = if mask & (1 << bit) != 0 { Spring::Damaged } else { Spring::Operational };
+            }
+            if groups_of(&springs) == groups {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn parse_input_test() {
+        let result = parse_input(INPUT);
+        assert_eq!(result.len(), 6);
+        assert_eq!(result[4].0, parse_springs("????.######..#####."));
+        assert_eq!(result[4].1, vec![1, 6, 5]);
+        assert_eq!(springs_to_string(&result[2].0), "?#?#?#?#?#?#?#?");
+
+        let result = parse_input("#.#");
+        assert_eq!(result[0].1, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn matches_pattern_test() {
+        let pattern = parse_springs("?###????????");
+        assert!(matches_pattern(&parse_springs(".###.##.#..."), &pattern));
+        assert!(matches_pattern(&parse_springs(".###....##.#"), &pattern));
+        assert!(!matches_pattern(&parse_springs("..##.##.#..."), &pattern));
+        assert!(!matches_pattern(&parse_springs(".###.##.#.."), &pattern));
+    }
+
+    #[test]
+    fn count_arrangements_test() {
+        let counts: Vec<u64> = parse_input(INPUT)
+            .iter()
+            .map(|(pattern, groups)| count_arrangements(pattern, groups))
+            .collect();
+        assert_eq!(counts, vec![1, 4, 1, 1, 4, 10]);
+    }
+
+    #[test]
+    fn count_unfolded_arrangements_test() {
+        let counts: Vec<u64> = parse_input(INPUT)
+            .iter()
+            .map(|(pattern, groups)| {
+                let (pattern, groups) = unfold(pattern, groups);
+                count_arrangements(&pattern, &groups)
+            })
+            .collect();
+        assert_eq!(counts, vec![1, 16384, 1, 16, 2500, 506250]);
+    }
+
+    #[test]
+    fn arrangements_test() {
+        assert_eq!(
+            listed("????????", &[3, 2]),
+            vec!["###.##..", "###..##.", "###...##", ".###.##.", ".###..##", "..###.##"]
+        );
+        assert_eq!(listed("????????????", &[3, 2, 1]).len(), 35);
+        assert_eq!(
+            listed("?###????????", &[3, 2, 1]),
+            vec![
+                ".###.##.#...",
+                ".###.##..#..",
+                ".###.##...#.",
+                ".###.##....#",
+                ".###..##.#..",
+                ".###..##..#.",
+                ".###..##...#",
+                ".###...##.#.",
+                ".###...##..#",
+                ".###....##.#",
+            ]
+        );
+        assert_eq!(listed(".??..??...?##.", &[1, 1, 3]).len(), 4);
+        assert_eq!(listed("#?#", &[3]), vec!["###"]);
+        assert_eq!(listed("#?#", &[1, 1]), vec!["#.#"]);
+        assert_eq!(listed("..?", &[]), vec!["..."]);
+        assert!(listed("#..", &[]).is_empty());
+        assert!(listed("???", &[2, 1, 1]).is_empty());
+    }
+
+    #[test]
+    fn arrangements_is_lazy_test() {
+        // Far too many arrangements to list, but the first few come out at once
+        let (pattern, groups) = unfold(&parse_springs("?###????????"), &[3, 2, 1]);
+        let first: Vec<String> = arrangements(&pattern, &groups)
+            .take(3)
+            .map(|springs| springs_to_string(&springs))
+            .collect();
+        assert_eq!(first.len(), 3);
+        assert!(first[0].starts_with(".###.##.#.....###.##.#"));
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            Day12::parse("#.? 1,1").unwrap(),
+            vec![(vec![Spring::Damaged, Spring::Operational, Spring::Unknown], vec![1, 1])]
+        );
+        assert_eq!(
+            Day12::parse("#.? 1\n#x 1").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Invalid spring: x".to_string() }
+        );
+        assert!(Day12::parse("#.? 1,a").is_err());
+        assert_eq!(
+            Day12::parse("#.? 0,1").unwrap_err(),
+            ParseError::Syntax { line: 1, message: "Empty group: 0,1".to_string() }
+        );
+        assert!(Day12::parse("#.? 1,00").is_err());
+        assert!(Day12::parse("#.? 10").is_ok());
+    }
+
+    fn springs_strategy() -> impl Strategy<Value = Vec<Spring>> {
+        prop::collection::vec(
+            prop_oneof![Just(Spring::Operational), Just(Spring::Damaged), Just(Spring::Unknown)],
+            0..14,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn count_matches_listing(pattern in springs_strategy(), groups in prop::collection::vec(1usize..5, 0..5)) {
+            let listing: Vec<Vec<Spring>> = arrangements(&pattern, &groups).collect();
+            prop_assert_eq!(count_arrangements(&pattern, &groups), listing.len() as u64);
+            prop_assert_eq!(listing.len() as u64, brute_force(&pattern, &groups));
+
+            for springs in listing.iter() {
+                prop_assert!(matches_pattern(springs, &pattern));
+                prop_assert_eq!(groups_of(springs), groups.clone());
+            }
+            for pair in listing.windows(2) {
+                prop_assert_ne!(&pair[0], &pair[1]);
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use aoc::{Answer, ParseError, Solution};
+
+// A chunk's rows and columns, each as a binary number
+pub type Chunk = (Vec<i64>, Vec<i64>);
+
+// The input is a list of lines. The lines are grouped into larger chunks
+// separated by blank lines. Each chunk has multiple lines with hash signs
+// and dots. The hash signs represent the bit 1, and the dots represent the
+// bit 0.
+//
+// Each chunk is converted into the binary representation of its rows and of
+// its columns, every one stored in an i64.
+pub fn parse_chunks(input: &str) -> Result<Vec<Chunk>, ParseError> {
+    let mut chunks = Vec::new();
+
+    let mut first_line = 1;
+    for chunk in input.split("\n\n") {
+        let mut grid: Vec<Vec<i64>> = Vec::new();
+        for (i, line) in chunk.lines().enumerate() {
+            let syntax = |message: String| ParseError::Syntax { line: first_line + i, message };
+            let mut row: Vec<i64> = Vec::new();
+            for c in line.chars() {
+                match c {
+                    '#' => row.push(1),
+                    '.' => row.push(0),
+                    _ => return Err(syntax(format!("Unexpected character: {}", c))),
+                }
+            }
+            if grid.first().is_some_and(|first| first.len() != row.len()) {
+                return Err(syntax("Rows differ in length".to_string()));
+            }
+            grid.push(row);
+        }
+        first_line += chunk.lines().count() + 1;
+        if grid.is_empty() {
+            continue;
+        }
+
+        // Convert the grid rows into a binary representation.
+        let row_binary: Vec<i64> = grid.iter().map(|row| row.iter().fold(0, |value, bit| value << 1 | bit)).collect();
+        let col_binary: Vec<i64> = (0..grid[0].len())
+            .map(|col| grid.iter().fold(0, |value, row| value << 1 | row[col]))
+            .collect();
+
+        // Add the chunk to the list of chunks.
+        chunks.push((row_binary, col_binary));
+    }
+
+    Ok(chunks)
+}
+
+// The first of the two lines either side of a mirror, if there is one
+fn find_mirror(lines: &[i64]) -> Option<usize> {
+    for i in 0..lines.len().saturating_sub(1) {
+        if lines[i] == lines[i + 1] {
+            // Backtrack here and make sure both sides are symmetrical
+            // all the way to the nearest edge.
+            let mut left = i;
+            let mut right = i + 1;
+            while left > 0 && right < lines.len() - 1 {
+                if lines[left - 1] != lines[right + 1] {
+                    break;
+                }
+                left -= 1;
+                right += 1;
+            }
+
+            if left == 0 || right == lines.len() - 1 {
+                return Some(i);
+            }
+        }
+    }
+
+    None
+}
+
+pub fn find_symmetry_line(chunk: &Chunk) -> (Option<usize>, Option<usize>) {
+    let (rows, cols) = chunk;
+    (find_mirror(rows), find_mirror(cols))
+}
+
+pub fn calculate_score(chunks: &[Chunk]) -> i64 {
+    let mut score = 0;
+
+    for chunk in chunks {
+        let (row_line, col_line) = find_symmetry_line(chunk);
+
+        if let Some(row_line) = row_line {
+            score += (row_line + 1) * 100;
+        }
+
+        if let Some(col_line) = col_line {
+            score += col_line + 1;
+        }
+    }
+
+    score as i64
+}
+
+pub struct Day13;
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Parsed = Vec<Chunk>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_chunks(input)
+    }
+
+    fn part1(chunks: &Self::Parsed) -> Answer {
+        calculate_score(chunks).into()
+    }
+
+    fn part2(_chunks: &Self::Parsed) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+aoc::register!(Day13);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn test_parse_single() {
+        let input = "#.#.#.#."; // 0b10101010 -> 170
+        let chunks = parse_chunks(input).unwrap();
+        assert_eq!(chunks.len(), 1);
+
+        let (rows, cols) = &chunks[0];
+        assert_eq!(rows.len(), 1);
+        assert_eq!(cols.len(), 8);
+
+        // There's a single row value
+        assert_eq!(rows[0], 170);
+
+        // There are a bunch of col values
+        assert_eq!(cols[0], 1);
+        assert_eq!(cols[1], 0);
+        assert_eq!(cols[2], 1);
+        assert_eq!(cols[3], 0);
+        assert_eq!(cols[4], 1);
+        assert_eq!(cols[5], 0);
+        assert_eq!(cols[6], 1);
+        assert_eq!(cols[7], 0);
+    }
+
+    #[test]
+    fn test_parse_two_chunks() {
+        let chunks = parse_chunks(INPUT).unwrap();
+        assert_eq!(chunks.len(), 2);
+
+        let num_row_values_in_chunk = [7, 7];
+        let num_col_values_in_chunk = [9, 9];
+
+        for (i, (rows, cols)) in chunks.iter().enumerate() {
+            assert_eq!(rows.len(), num_row_values_in_chunk[i]);
+            assert_eq!(cols.len(), num_col_values_in_chunk[i]);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_chunks("#.\n..\n\n#.\n.x").unwrap_err(),
+            ParseError::Syntax { line: 5, message: "Unexpected character: x".to_string() }
+        );
+        assert_eq!(
+            parse_chunks("#.\n.").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Rows differ in length".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_find_symmetry() {
+        let chunks = parse_chunks(INPUT).unwrap();
+        assert_eq!(chunks.len(), 2);
+
+        let (row_start, col_start) = find_symmetry_line(&chunks[0]);
+        assert_eq!(row_start, None);
+        assert_eq!(col_start, Some(4));
+
+        let (row_start, col_start) = find_symmetry_line(&chunks[1]);
+        assert_eq!(row_start, Some(3));
+        assert_eq!(col_start, None);
+    }
+
+    #[test]
+    fn test_calculate_score() {
+        let chunks = parse_chunks(INPUT).unwrap();
+        assert_eq!(chunks.len(), 2);
+
+        let result = calculate_score(&chunks);
+        assert_eq!(result, 405);
+    }
+}
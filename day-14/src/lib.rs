@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use aoc::{Answer, ParseError, Solution};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Tile {
+    Round,
+    Square,
+    Empty,
+}
+impl Tile {
+    pub fn from_char(c: char) -> Option<Tile> {
+        match c {
+            'O' => Some(Tile::Round),
+            '#' => Some(Tile::Square),
+            '.' => Some(Tile::Empty),
+            _ => None,
+        }
+    }
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Round => 'O',
+            Tile::Square => '#',
+            Tile::Empty => '.',
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Board {
+    tiles: Vec<Vec<Tile>>,
+}
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.tiles.iter() {
+            for tile in row.iter() {
+                write!(f, "{}", tile.to_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    pub fn roll_north(&mut self) {
+        for row in 1..self.tiles.len() {
+            for col in 0..self.tiles[row].len() {
+                if self.tiles[row][col] == Tile::Round {
+                    // Roll up past every empty tile above.
+                    let mut replace_row = row;
+                    while replace_row > 0 && self.tiles[replace_row - 1][col] == Tile::Empty {
+                        replace_row -= 1;
+                    }
+
+                    self.tiles[row][col] = Tile::Empty;
+                    self.tiles[replace_row][col] = Tile::Round;
+                }
+            }
+        }
+    }
+
+    pub fn roll_south(&mut self) {
+        for row in (0..self.tiles.len() - 1).rev() {
+            for col in 0..self.tiles[row].len() {
+                if self.tiles[row][col] == Tile::Round {
+                    // Roll down past every empty tile below.
+                    let mut replace_row = row;
+                    while replace_row + 1 < self.tiles.len() && self.tiles[replace_row + 1][col] == Tile::Empty {
+                        replace_row += 1;
+                    }
+
+                    self.tiles[row][col] = Tile::Empty;
+                    self.tiles[replace_row][col] = Tile::Round;
+                }
+            }
+        }
+    }
+
+    pub fn roll_west(&mut self) {
+        for col in 1..self.tiles[0].len() {
+            for row in 0..self.tiles.len() {
+                if self.tiles[row][col] == Tile::Round {
+                    // Roll left past every empty tile to the left.
+                    let mut replace_col = col;
+                    while replace_col > 0 && self.tiles[row][replace_col - 1] == Tile::Empty {
+                        replace_col -= 1;
+                    }
+
+                    self.tiles[row][col] = Tile::Empty;
+                    self.tiles[row][replace_col] = Tile::Round;
+                }
+            }
+        }
+    }
+
+    pub fn roll_east(&mut self) {
+        for col in (0..self.tiles[0].len() - 1).rev() {
+            for row in 0..self.tiles.len() {
+                if self.tiles[row][col] == Tile::Round {
+                    // Roll right past every empty tile to the right.
+                    let mut replace_col = col;
+                    while replace_col + 1 < self.tiles[row].len() && self.tiles[row][replace_col + 1] == Tile::Empty {
+                        replace_col += 1;
+                    }
+
+                    self.tiles[row][col] = Tile::Empty;
+                    self.tiles[row][replace_col] = Tile::Round;
+                }
+            }
+        }
+    }
+
+    pub fn cycle(&mut self) {
+        self.roll_north();
+        self.roll_west();
+        self.roll_south();
+        self.roll_east();
+    }
+
+    pub fn weight(&self) -> i64 {
+        let mut result = 0;
+        for (i, row) in self.tiles.iter().enumerate() {
+            for tile in row.iter() {
+                if *tile == Tile::Round {
+                    result += self.tiles.len() as i64 - i as i64;
+                }
+            }
+        }
+        result
+    }
+
+    // The board after spinning it num_cycles times. The rocks soon settle
+    // into a loop, so once a board comes around again the rest of the
+    // cycles can be skipped.
+    pub fn after_cycles(&self, num_cycles: u64) -> Board {
+        let mut board = self.clone();
+        let mut seen: HashMap<Board, u64> = HashMap::new();
+        let mut i = 0;
+        while i < num_cycles {
+            if let Some(first) = seen.insert(board.clone(), i) {
+                let remaining = (num_cycles - i) % (i - first);
+                for _ in 0..remaining {
+                    board.cycle();
+                }
+                return board;
+            }
+            board.cycle();
+            i += 1;
+        }
+        board
+    }
+}
+
+pub fn parse(input: &str) -> Result<Board, ParseError> {
+    let mut tiles = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let mut row = Vec::new();
+        for c in line.chars() {
+            let tile = Tile::from_char(c).ok_or_else(|| ParseError::Syntax {
+                line: i + 1,
+                message: format!("Unknown tile: {}", c),
+            })?;
+            row.push(tile);
+        }
+        tiles.push(row);
+    }
+    if tiles.is_empty() || tiles.iter().any(|row| row.len() != tiles[0].len() || row.is_empty()) {
+        return Err(ParseError::Invalid("Expected a rectangular board".to_string()));
+    }
+    Ok(Board { tiles })
+}
+
+pub struct Day14;
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Parsed = Board;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1(board: &Self::Parsed) -> Answer {
+        let mut board = board.clone();
+        board.roll_north();
+        board.weight().into()
+    }
+
+    fn part2(board: &Self::Parsed) -> Answer {
+        board.after_cycles(1_000_000_000).weight().into()
+    }
+}
+
+aoc::register!(Day14);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn test_parse() {
+        let board = parse(INPUT).unwrap();
+        assert_eq!(board.to_string().trim(), INPUT.trim());
+
+        assert_eq!(
+            parse("O.\n.x").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Unknown tile: x".to_string() }
+        );
+        assert!(parse("O.\n.").is_err());
+    }
+
+    #[test]
+    fn test_roll_north() {
+        let mut board = parse(INPUT).unwrap();
+        board.roll_north();
+
+        assert_eq!(
+            board.to_string().trim(),
+            "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#...."
+        );
+    }
+
+    #[test]
+    fn test_roll_south() {
+        let mut board = parse(INPUT).unwrap();
+        board.roll_south();
+
+        assert_eq!(
+            board.to_string().trim(),
+            ".....#....
+....#....#
+...O.##...
+...#......
+O.O....O#O
+O.#..O.#.#
+O....#....
+OO....OO..
+#OO..###..
+#OO.O#...O"
+        );
+    }
+
+    #[test]
+    fn test_roll_west() {
+        let mut board = parse(INPUT).unwrap();
+        board.roll_west();
+
+        assert_eq!(
+            board.to_string().trim(),
+            "O....#....
+OOO.#....#
+.....##...
+OO.#OO....
+OO......#.
+O.#O...#.#
+O....#OO..
+O.........
+#....###..
+#OO..#...."
+        );
+    }
+
+    #[test]
+    fn test_roll_east() {
+        let mut board = parse(INPUT).unwrap();
+        board.roll_east();
+
+        assert_eq!(
+            board.to_string().trim(),
+            "....O#....
+.OOO#....#
+.....##...
+.OO#....OO
+......OO#.
+.O#...O#.#
+....O#..OO
+.........O
+#....###..
+#..OO#...."
+        );
+    }
+
+    #[test]
+    fn test_one_cycle() {
+        let mut board = parse(INPUT).unwrap();
+        board.cycle();
+
+        assert_eq!(
+            board.to_string().trim(),
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#...."
+        );
+    }
+
+    #[test]
+    fn test_weight() {
+        let mut board = parse(INPUT).unwrap();
+
+        board.roll_north();
+        assert_eq!(board.weight(), 136);
+    }
+
+    #[test]
+    fn test_after_cycles() {
+        // Skipping ahead gives the same boards as spinning all the way
+        let board = parse(INPUT).unwrap();
+        let mut spun = board.clone();
+        for num_cycles in 0..40 {
+            assert_eq!(board.after_cycles(num_cycles), spun);
+            spun.cycle();
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+
+use aoc::{Answer, ParseError, Solution};
+use nom::{
+    character::complete::{alpha1, digit1, one_of},
+    combinator::{map_res, recognize},
+    IResult,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Instruction {
+    pub input: String,
+    pub label: String,
+    pub operation: char,
+    pub focal_length: u64,
+}
+
+pub struct Boxes {
+    boxes: Vec<Vec<Instruction>>,
+}
+impl Boxes {
+    pub fn new(size: usize) -> Self {
+        Self { boxes: vec![Vec::new(); size] }
+    }
+
+    pub fn add(&mut self, instr: Instruction) {
+        let picked_box = &mut self.boxes[do_hash(&instr.label) as usize];
+        let index = picked_box.iter().position(|lens| lens.label == instr.label);
+
+        if instr.operation == '-' {
+            // Remove any instruction with the same label from the box
+            if let Some(index) = index {
+                picked_box.remove(index);
+            }
+        } else if instr.operation == '=' {
+            // Replace any instruction with the same label from the box,
+            // or add it if it doesn't exist
+            match index {
+                Some(index) => picked_box[index] = instr,
+                None => picked_box.push(instr),
+            }
+        }
+    }
+
+    pub fn compute_focusing_power(&self) -> u64 {
+        let mut power: u64 = 0;
+        for (i, picked_box) in self.boxes.iter().enumerate() {
+            for (slot, instr) in picked_box.iter().enumerate() {
+                power += (i as u64 + 1) * (slot as u64 + 1) * instr.focal_length;
+            }
+        }
+
+        power
+    }
+}
+impl fmt::Display for Boxes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, picked_box) in self.boxes.iter().enumerate() {
+            write!(f, "Box {}: ", i)?;
+            for instr in picked_box {
+                write!(f, "[{} {}] ", instr.label, instr.focal_length)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn my_u64(input: &str) -> IResult<&str, u64> {
+    map_res(recognize(digit1), str::parse)(input)
+}
+
+pub fn parse_entry(input: &str) -> IResult<&str, Instruction> {
+    let orig_input = input;
+    let (input, label) = alpha1(input)?;
+    let (input, operation) = one_of("=-")(input)?;
+    let (input, focal_length) = if operation == '-' { (input, 0) } else { my_u64(input)? };
+
+    Ok((
+        input,
+        Instruction {
+            input: orig_input.to_string(),
+            label: label.to_string(),
+            operation,
+            focal_length,
+        },
+    ))
+}
+
+// The comma separated steps, on however many lines they come
+pub fn parse_steps(input: &str) -> Vec<&str> {
+    input.lines().flat_map(|line| line.split(',')).collect()
+}
+
+pub fn do_hash(input: &str) -> u64 {
+    let mut result = 0;
+    for c in input.bytes() {
+        result += c as u64;
+        result *= 17;
+        result %= 256;
+    }
+    result
+}
+
+pub fn follow_instructions(instructions: &[Instruction]) -> Boxes {
+    let mut boxes: Boxes = Boxes::new(256);
+
+    for instr in instructions {
+        boxes.add(instr.clone());
+    }
+
+    boxes
+}
+
+pub struct Day15;
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut result: Vec<Instruction> = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            // Use nom to parse each part into a Instruction struct
+            for part in line.split(',') {
+                match parse_entry(part) {
+                    Ok(("", instruction)) => result.push(instruction),
+                    _ => {
+                        return Err(ParseError::Syntax { line: i + 1, message: format!("Invalid step: {}", part) })
+                    },
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn part1(instructions: &Self::Parsed) -> Answer {
+        // The whole step is hashed, not just the label
+        instructions.iter().map(|instr| do_hash(&instr.input)).sum::<u64>().into()
+    }
+
+    fn part2(instructions: &Self::Parsed) -> Answer {
+        follow_instructions(instructions).compute_focusing_power().into()
+    }
+}
+
+aoc::register!(Day15);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn test_parse() {
+        let result = parse_steps(INPUT);
+        assert_eq!(result, vec!["rn=1", "cm-", "qp=3", "cm=2", "qp-", "pc=4", "ot=9", "ab=5", "pc-", "pc=6", "ot=7"]);
+
+        let instructions = Day15::parse(INPUT).unwrap();
+        assert_eq!(instructions.len(), 11);
+        assert_eq!(
+            instructions[2],
+            Instruction { input: "qp=3".to_string(), label: "qp".to_string(), operation: '=', focal_length: 3 }
+        );
+        assert_eq!(
+            Day15::parse("rn=1,cm*").unwrap_err(),
+            ParseError::Syntax { line: 1, message: "Invalid step: cm*".to_string() }
+        );
+    }
+
+    // Test cases: rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+    #[test_log::test(rstest)]
+    #[case("rn=1", 30)]
+    #[case("rn", 0)]
+    #[case("cm-", 253)]
+    #[case("qp=3", 97)]
+    #[case("qp", 1)]
+    #[case("cm=2", 47)]
+    #[case("qp-", 14)]
+    #[case("pc=4", 180)]
+    #[case("ot=9", 9)]
+    #[case("ab=5", 197)]
+    #[case("pc-", 48)]
+    #[case("pc=6", 214)]
+    #[case("ot=7", 231)]
+    fn test_do_hash(#[case] input: &str, #[case] expected: u64) {
+        let result = do_hash(input);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_box_print() {
+        let mut boxes = Boxes::new(3);
+        boxes.add(Instruction {
+            input: "rn=1".to_string(),
+            label: "rn".to_string(),
+            operation: '=',
+            focal_length: 1,
+        });
+        assert_eq!(boxes.to_string(), "Box 0: [rn 1] \nBox 1: \nBox 2: \n".to_string());
+    }
+
+    #[test]
+    fn test_add_boxes() {
+        let mut boxes = Boxes::new(4);
+        let instructions = Day15::parse(INPUT).unwrap();
+
+        for instruction in instructions {
+            boxes.add(instruction);
+        }
+
+        assert_eq!(
+            boxes.to_string(),
+            "Box 0: [rn 1] [cm 2] \nBox 1: \nBox 2: \nBox 3: [ot 7] [ab 5] [pc 6] \n".to_string()
+        );
+    }
+
+    #[test]
+    fn test_power() {
+        let boxes = follow_instructions(&Day15::parse(INPUT).unwrap());
+        assert_eq!(boxes.compute_focusing_power(), 145);
+    }
+}
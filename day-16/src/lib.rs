@@ -0,0 +1,1019 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use aoc::{Answer, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up = 0,
+    Down = 1,
+    Left = 2,
+    Right = 3,
+}
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Syntax { line: usize, message: String },
+}
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "could not read optics table: {}", error),
+            ConfigError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+impl std::error::Error for ConfigError {}
+
+// Bits for every direction, which is how a beam passes through empty space
+const PASS_THROUGH: [u8; 4] = [1, 2, 4, 8];
+
+// Where beams go on each kind of tile: for every tile character, a bitmask of
+// the directions a beam leaves it in, indexed by the direction it entered
+// moving in. Tiles that let every beam straight through cost nothing to
+// simulate, anything else is an optic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Optics {
+    turns: [Option<[u8; 4]>; 256],
+}
+impl Optics {
+    // One tile per line, see optics.txt for the format
+    pub fn parse(config: &str) -> Result<Self, ConfigError> {
+        let mut turns = [None; 256];
+        for (number, line) in config.lines().enumerate() {
+            let syntax = |message: String| ConfigError::Syntax { line: number + 1, message };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.is_empty() || line.trim_start().starts_with("//") {
+                continue;
+            }
+            if fields.len() != 5 {
+                return Err(syntax(format!("expected a tile and 4 directions, found {} fields", fields.len())));
+            }
+
+            let tile = match fields[0].as_bytes() {
+                [tile] => *tile,
+                _ => return Err(syntax(format!("tile {:?} is not a single ASCII character", fields[0]))),
+            };
+            if turns[tile as usize].is_some() {
+                return Err(syntax(format!("tile {} is defined twice", tile as char)));
+            }
+
+            let mut tile_turns = [0; 4];
+            for (mask, field) in tile_turns.iter_mut().zip(&fields[1..]) {
+                if *field == "-" {
+                    continue;
+                }
+                for c in field.chars() {
+                    let direction = match c {
+                        'U' => Direction::Up,
+                        'D' => Direction::Down,
+                        'L' => Direction::Left,
+                        'R' => Direction::Right,
+                        _ => return Err(syntax(format!("unknown direction {}", c))),
+                    };
+                    if *mask & direction.bit() != 0 {
+                        return Err(syntax(format!("direction {} is repeated", c)));
+                    }
+                    *mask |= direction.bit();
+                }
+            }
+            turns[tile as usize] = Some(tile_turns);
+        }
+
+        Ok(Self { turns })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let config = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::parse(&config)
+    }
+
+    // The directions a beam leaves the tile in, or None if the tile isn't in
+    // the table
+    pub fn outgoing(&self, tile: char, direction: Direction) -> Option<Vec<Direction>> {
+        let turns = self.turns(u8::try_from(tile).ok()?)?[direction as usize];
+        Some(Direction::ALL.into_iter().filter(|leaving| turns & leaving.bit() != 0).collect())
+    }
+
+    fn turns(&self, tile: u8) -> Option<[u8; 4]> {
+        self.turns[tile as usize]
+    }
+}
+impl Default for Optics {
+    // The tiles from the puzzle
+    fn default() -> Self {
+        Self::parse(include_str!("../optics.txt")).unwrap()
+    }
+}
+
+// A beam entering the tile at (x, y) moving in the given direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub x: usize,
+    pub y: usize,
+    pub direction: Direction,
+}
+
+const NOWHERE: u32 = u32::MAX;
+
+// A tile that doesn't let beams straight through, i.e. the only places a beam
+// can change direction
+#[derive(Debug, Clone)]
+struct Optic {
+    x: u32,
+    y: u32,
+    turns: [u8; 4],
+    // The next optic a beam leaving in each direction runs into, or NOWHERE
+    // if it leaves the field first
+    next: [u32; 4],
+}
+
+impl Optic {
+    fn position(&self) -> (usize, usize) {
+        (self.x as usize, self.y as usize)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    tiles: Vec<u8>,
+    width: usize,
+    height: usize,
+    optics: Vec<Optic>,
+    // Index into optics for every tile, NOWHERE for empty ones
+    optic_at: Vec<u32>,
+}
+impl Field {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        Self::with_optics(input, &Optics::default())
+    }
+
+    // Fails on tiles that aren't in the table and on rows that differ in
+    // length, blank lines are skipped
+    pub fn with_optics(input: &str, table: &Optics) -> Result<Self, ParseError> {
+        let mut tiles = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        let mut optics = Vec::new();
+        let mut optic_at = Vec::new();
+        for (i, line) in input.lines().enumerate().filter(|(_, line)| !line.is_empty()) {
+            let syntax = |message: String| ParseError::Syntax { line: i + 1, message };
+            for (x, tile) in line.chars().enumerate() {
+                let Some(turns) = u8::try_from(tile).ok().and_then(|tile| table.turns(tile)) else {
+                    return Err(syntax(format!("Unknown tile {} at column {}", tile, x + 1)));
+                };
+                if turns == PASS_THROUGH {
+                    optic_at.push(NOWHERE);
+                } else {
+                    optic_at.push(optics.len() as u32);
+                    optics.push(Optic { x: x as u32, y: height as u32, turns, next: [NOWHERE; 4] });
+                }
+            }
+            // Every tile is ASCII by now, so bytes and columns agree
+            if *width.get_or_insert(line.len()) != line.len() {
+                return Err(syntax(format!("Row of {} tiles, the rows before have {}", line.len(), width.unwrap())));
+            }
+            tiles.extend(line.bytes());
+            height += 1;
+        }
+        let width = width.unwrap_or(0);
+
+        let mut field = Self { tiles, width, height, optics, optic_at };
+        for id in 0..field.optics.len() {
+            let (x, y) = field.optics[id].position();
+            for direction in Direction::ALL {
+                field.optics[id].next[direction as usize] = match field.step(x, y, direction) {
+                    Some((x, y)) => field.find_optic(x, y, direction).0,
+                    None => NOWHERE,
+                };
+            }
+        }
+        Ok(field)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_tile(&self, x: usize, y: usize) -> char {
+        self.tiles[y * self.width + x] as char
+    }
+
+    fn step(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
+        match direction {
+            Direction::Up if y > 0 => Some((x, y - 1)),
+            Direction::Down if y + 1 < self.height => Some((x, y + 1)),
+            Direction::Left if x > 0 => Some((x - 1, y)),
+            Direction::Right if x + 1 < self.width => Some((x + 1, y)),
+            _ => None,
+        }
+    }
+
+    // Walks from (x, y) in the given direction until it reaches an optic,
+    // returning it along with the last tile the beam passes through, which is
+    // the optic itself or the tile at the edge.
+    fn find_optic(&self, mut x: usize, mut y: usize, direction: Direction) -> (u32, usize, usize) {
+        loop {
+            let id = self.optic_at[y * self.width + x];
+            if id != NOWHERE {
+                return (id, x, y);
+            }
+            match self.step(x, y, direction) {
+                Some(next) => (x, y) = next,
+                None => return (NOWHERE, x, y),
+            }
+        }
+    }
+
+    // The last tile a beam leaving the optic in the given direction passes
+    // through
+    fn run_end(&self, optic: &Optic, direction: Direction) -> (usize, usize) {
+        let (x, y) = optic.position();
+        match optic.next[direction as usize] {
+            NOWHERE => match direction {
+                Direction::Up => (x, 0),
+                Direction::Down => (x, self.height - 1),
+                Direction::Left => (0, y),
+                Direction::Right => (self.width - 1, y),
+            },
+            next => self.optics[next as usize].position(),
+        }
+    }
+
+    // The state a beam from the entry first reaches, if any, along with the
+    // last tile it passes through on the way
+    fn entry_state(&self, entry: Entry) -> (Option<u32>, (usize, usize)) {
+        let (id, x, y) = self.find_optic(entry.x, entry.y, entry.direction);
+        let start = (id != NOWHERE).then(|| to_state(id, entry.direction));
+        (start, (x, y))
+    }
+
+    // The directions a beam leaves an optic in after entering it moving in
+    // the given direction
+    fn leaving(&self, id: u32, direction: Direction) -> impl Iterator<Item = Direction> {
+        let turns = self.optics[id as usize].turns[direction as usize];
+        Direction::ALL.into_iter().filter(move |leaving| turns & leaving.bit() != 0)
+    }
+
+    fn next_states(&self, state: u32) -> impl Iterator<Item = u32> + '_ {
+        let (id, direction) = from_state(state);
+        self.leaving(id, direction).filter_map(move |leaving| {
+            let next = self.optics[id as usize].next[leaving as usize];
+            (next != NOWHERE).then(|| to_state(next, leaving))
+        })
+    }
+
+    // Every way a beam can enter the field from outside
+    pub fn entries(&self) -> Vec<Entry> {
+        let mut entries = Vec::new();
+        for y in 0..self.height {
+            entries.push(Entry { x: 0, y, direction: Direction::Right });
+            entries.push(Entry { x: self.width - 1, y, direction: Direction::Left });
+        }
+        for x in 0..self.width {
+            entries.push(Entry { x, y: 0, direction: Direction::Down });
+            entries.push(Entry { x, y: self.height - 1, direction: Direction::Up });
+        }
+        entries
+    }
+
+    // Follows the beam from the entry and returns the number of energized
+    // tiles. The worklist holds (optic, direction) states, each of which is
+    // only expanded once, and the straight runs of empty tiles between optics
+    // are marked a word at a time rather than walked.
+    pub fn energize(&self, entry: Entry, beams: &mut Beams) -> usize {
+        beams.reset(self);
+
+        let (id, x, y) = self.find_optic(entry.x, entry.y, entry.direction);
+        beams.mark_run(entry.x, entry.y, x, y);
+        if id != NOWHERE {
+            beams.stack.push((id, entry.direction));
+        }
+
+        while let Some((id, direction)) = beams.stack.pop() {
+            if !beams.visit(id, direction) {
+                continue;
+            }
+
+            let optic = &self.optics[id as usize];
+            for next_direction in self.leaving(id, direction) {
+                let (x, y) = optic.position();
+                let (end_x, end_y) = self.run_end(optic, next_direction);
+                beams.mark_run(x, y, end_x, end_y);
+                let next = optic.next[next_direction as usize];
+                if next != NOWHERE {
+                    beams.stack.push((next, next_direction));
+                }
+            }
+        }
+
+        beams.count_energized()
+    }
+
+    pub fn max_energized(&self) -> usize {
+        let mut beams = Beams::new(self);
+        self.entries()
+            .into_iter()
+            .map(|entry| self.energize(entry, &mut beams))
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Same as max_energized, with the entries spread over rayon's thread
+    // pool and one set of beams per worker
+    #[cfg(feature = "parallel")]
+    pub fn max_energized_parallel(&self) -> usize {
+        self.entries()
+            .into_par_iter()
+            .map_init(|| Beams::new(self), |beams, entry| self.energize(entry, beams))
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Same as max_energized, but shares the work between entries through the
+    // condensed beam graph
+    pub fn max_energized_condensed(&self) -> usize {
+        BeamGraph::new(self).energize_all().into_iter().max().unwrap_or(0)
+    }
+}
+
+// The states a beam can be in, an optic along with the direction it entered
+// moving in, packed as optic * 4 + direction
+fn to_state(id: u32, direction: Direction) -> u32 {
+    id * 4 + direction as u32
+}
+
+fn from_state(state: u32) -> (u32, Direction) {
+    (state / 4, Direction::ALL[state as usize % 4])
+}
+
+// A tile bitset, one bit per tile, row by row
+fn mark_tiles(bits: &mut [u64], width: usize, (x1, y1): (usize, usize), (x2, y2): (usize, usize)) {
+    if y1 == y2 {
+        set_bits(bits, y1 * width + x1.min(x2), y1 * width + x1.max(x2));
+    } else {
+        for y in y1.min(y2)..=y1.max(y2) {
+            let bit = y * width + x1;
+            bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+}
+
+const UNVISITED: u32 = u32::MAX;
+
+// The graph of beam segments between optics, condensed into its strongly
+// connected components. Every state in a component reaches the same states,
+// so the tiles energized from a state are the segments of its own component
+// together with those of every component downstream of it.
+#[derive(Debug, Clone)]
+pub struct BeamGraph<'a> {
+    field: &'a Field,
+    // Component of every state reachable from the edges, UNVISITED for the
+    // rest
+    component: Vec<u32>,
+    // States in each component. Components are numbered in the order
+    // Tarjan's algorithm completes them, so successors come before the
+    // components leading to them.
+    members: Vec<Vec<u32>>,
+    successors: Vec<Vec<u32>>,
+}
+impl<'a> BeamGraph<'a> {
+    pub fn new(field: &'a Field) -> Self {
+        let states = field.optics.len() * 4;
+        let mut index = vec![UNVISITED; states];
+        let mut lowlink = vec![0; states];
+        let mut on_stack = vec![false; states];
+        let mut stack = Vec::new();
+        let mut component = vec![UNVISITED; states];
+        let mut members = Vec::new();
+        let mut counter = 0;
+
+        // Tarjan's algorithm, with an explicit stack of (state, next
+        // direction to try) in place of recursion
+        let mut calls: Vec<(u32, usize)> = Vec::new();
+        for entry in field.entries() {
+            let Some(root) = field.entry_state(entry).0 else {
+                continue;
+            };
+            if index[root as usize] != UNVISITED {
+                continue;
+            }
+            index[root as usize] = counter;
+            lowlink[root as usize] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root as usize] = true;
+            calls.push((root, 0));
+
+            while let Some((current, tried)) = calls.last_mut() {
+                let current = *current;
+                if let Some(next) = field.next_states(current).nth(*tried) {
+                    *tried += 1;
+                    if index[next as usize] == UNVISITED {
+                        index[next as usize] = counter;
+                        lowlink[next as usize] = counter;
+                        counter += 1;
+                        stack.push(next);
+                        on_stack[next as usize] = true;
+                        calls.push((next, 0));
+                    } else if on_stack[next as usize] {
+                        lowlink[current as usize] = lowlink[current as usize].min(index[next as usize]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some((parent, _)) = calls.last() {
+                    lowlink[*parent as usize] = lowlink[*parent as usize].min(lowlink[current as usize]);
+                }
+                if lowlink[current as usize] == index[current as usize] {
+                    let mut group = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member as usize] = false;
+                        component[member as usize] = members.len() as u32;
+                        group.push(member);
+                        if member == current {
+                            break;
+                        }
+                    }
+                    members.push(group);
+                }
+            }
+        }
+
+        let successors = members
+            .iter()
+            .enumerate()
+            .map(|(id, group)| {
+                let mut next: Vec<u32> = group
+                    .iter()
+                    .flat_map(|member| field.next_states(*member))
+                    .map(|next| component[next as usize])
+                    .filter(|next| *next != id as u32)
+                    .collect();
+                next.sort_unstable();
+                next.dedup();
+                next
+            })
+            .collect();
+
+        Self { field, component, members, successors }
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.members.len()
+    }
+
+    // The number of energized tiles for every entry, in the order of
+    // Field::entries.
+    //
+    // Components are visited successors first. A component that's reached
+    // from more than one place, be it an entry or another component, gets
+    // its tile set stored once it's complete and dropped again after its
+    // last use. Any other component has a single way into it, so its tiles
+    // are added straight into the set of whatever leads to it.
+    pub fn energize_all(&self) -> Vec<usize> {
+        let field = self.field;
+        let words = (field.width * field.height).div_ceil(64);
+        let entries = field.entries();
+        let starts: Vec<(Option<u32>, (usize, usize))> =
+            entries.iter().map(|entry| field.entry_state(*entry)).collect();
+
+        let mut entries_at = vec![Vec::new(); self.members.len()];
+        let mut uses = vec![0usize; self.members.len()];
+        for (i, (start, _)) in starts.iter().enumerate() {
+            if let Some(start) = start {
+                let component = self.component[*start as usize] as usize;
+                entries_at[component].push(i);
+                uses[component] += 1;
+            }
+        }
+        for next in self.successors.iter().flatten() {
+            uses[*next as usize] += 1;
+        }
+
+        let mut results = vec![0; entries.len()];
+        let mut stored: Vec<Option<Vec<u64>>> = vec![None; self.members.len()];
+        let mut pending = Vec::new();
+        for component in 0..self.members.len() {
+            if uses[component] < 2 && entries_at[component].is_empty() {
+                continue;
+            }
+
+            let mut tiles = vec![0u64; words];
+            pending.push(component as u32);
+            while let Some(current) = pending.pop() {
+                for member in &self.members[current as usize] {
+                    self.mark_segments(&mut tiles, *member);
+                }
+                for next in &self.successors[current as usize] {
+                    let next = *next as usize;
+                    match &stored[next] {
+                        Some(next_tiles) => {
+                            tiles.iter_mut().zip(next_tiles).for_each(|(word, next_word)| *word |= next_word);
+                            uses[next] -= 1;
+                            if uses[next] == 0 {
+                                stored[next] = None;
+                            }
+                        }
+                        None => pending.push(next as u32),
+                    }
+                }
+            }
+
+            for i in &entries_at[component] {
+                let mut energized = tiles.clone();
+                let entry = entries[*i];
+                mark_tiles(&mut energized, field.width, (entry.x, entry.y), starts[*i].1);
+                results[*i] = count_bits(&energized);
+                uses[component] -= 1;
+            }
+            if uses[component] > 0 {
+                stored[component] = Some(tiles);
+            }
+        }
+
+        // Entries that leave the field without hitting a single optic
+        for (i, (start, end)) in starts.iter().enumerate() {
+            if start.is_none() {
+                let (x, y) = (entries[i].x, entries[i].y);
+                results[i] = x.abs_diff(end.0) + y.abs_diff(end.1) + 1;
+            }
+        }
+
+        results
+    }
+
+    // Marks the runs a beam in the given state sets off along
+    fn mark_segments(&self, tiles: &mut [u64], state: u32) {
+        let field = self.field;
+        let (id, direction) = from_state(state);
+        let optic = &field.optics[id as usize];
+        for leaving in field.leaving(id, direction) {
+            mark_tiles(tiles, field.width, optic.position(), field.run_end(optic, leaving));
+        }
+    }
+}
+
+fn set_bits(bits: &mut [u64], start: usize, end: usize) {
+    // Inclusive range, start <= end
+    let (first, last) = (start / 64, end / 64);
+    let low = !0u64 << (start % 64);
+    let high = !0u64 >> (63 - end % 64);
+    if first == last {
+        bits[first] |= low & high;
+    } else {
+        bits[first] |= low;
+        bits[first + 1..last].fill(!0);
+        bits[last] |= high;
+    }
+}
+
+fn count_bits(bits: &[u64]) -> usize {
+    bits.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+// Transposes a 64x64 bit matrix stored as one word per row, bit i being
+// column i, by swapping ever smaller off-diagonal blocks.
+fn transpose(block: &mut [u64; 64]) {
+    let mut width = 32;
+    let mut mask: u64 = 0x0000_0000_ffff_ffff;
+    while width > 0 {
+        let mut row = 0;
+        while row < 64 {
+            for i in row..row + width {
+                let swap = ((block[i] >> width) ^ block[i + width]) & mask;
+                block[i] ^= swap << width;
+                block[i + width] ^= swap;
+            }
+            row += 2 * width;
+        }
+        width /= 2;
+        mask ^= mask << width;
+    }
+}
+
+// Scratch space for following beams, meant to be reused between entries.
+// Energized tiles are kept in two bitsets, one row by row for horizontal runs
+// and one column by column for vertical runs, so that either kind can be
+// filled a word at a time.
+#[derive(Debug, Clone, Default)]
+pub struct Beams {
+    // Four bits per optic, one for each direction a beam has entered it in
+    visited: Vec<u64>,
+    stack: Vec<(u32, Direction)>,
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+    row_words: usize,
+    column_words: usize,
+}
+impl Beams {
+    pub fn new(field: &Field) -> Self {
+        let mut beams = Self::default();
+        beams.reset(field);
+        beams
+    }
+
+    fn reset(&mut self, field: &Field) {
+        self.visited.clear();
+        self.visited.resize((field.optics.len() * 4).div_ceil(64), 0);
+        self.stack.clear();
+
+        // Round both up to whole 64x64 blocks, which is what counting needs
+        self.row_words = field.width.div_ceil(64);
+        self.column_words = field.height.div_ceil(64);
+        self.rows.clear();
+        self.rows.resize(self.column_words * 64 * self.row_words, 0);
+        self.columns.clear();
+        self.columns.resize(self.row_words * 64 * self.column_words, 0);
+    }
+
+    // Marks the state as visited, returning false if it already was
+    fn visit(&mut self, id: u32, direction: Direction) -> bool {
+        let bit = id as usize * 4 + direction as usize;
+        let mask = 1 << (bit % 64);
+        let word = &mut self.visited[bit / 64];
+        let new = *word & mask == 0;
+        *word |= mask;
+        new
+    }
+
+    // Energizes every tile on the straight line between two tiles
+    fn mark_run(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        if y1 == y2 {
+            let row = y1 * self.row_words * 64;
+            set_bits(&mut self.rows, row + x1.min(x2), row + x1.max(x2));
+        } else {
+            let column = x1 * self.column_words * 64;
+            set_bits(&mut self.columns, column + y1.min(y2), column + y1.max(y2));
+        }
+    }
+
+    // Tiles can be in both bitsets, so the overlap is counted by transposing
+    // the columns a block at a time and subtracting what they share with the
+    // rows.
+    pub fn count_energized(&self) -> usize {
+        let mut overlap = 0;
+        let mut block = [0u64; 64];
+        for block_x in 0..self.row_words {
+            for block_y in 0..self.column_words {
+                for (i, word) in block.iter_mut().enumerate() {
+                    *word = self.columns[(block_x * 64 + i) * self.column_words + block_y];
+                }
+                transpose(&mut block);
+                for (i, word) in block.iter().enumerate() {
+                    overlap += (word & self.rows[(block_y * 64 + i) * self.row_words + block_x]).count_ones() as usize;
+                }
+            }
+        }
+        count_bits(&self.rows) + count_bits(&self.columns) - overlap
+    }
+
+    pub fn is_energized(&self, x: usize, y: usize) -> bool {
+        let row_bit = y * self.row_words * 64 + x;
+        let column_bit = x * self.column_words * 64 + y;
+        (self.rows[row_bit / 64] >> (row_bit % 64)) & 1 == 1
+            || (self.columns[column_bit / 64] >> (column_bit % 64)) & 1 == 1
+    }
+
+    pub fn get_beams_string(&self, field: &Field) -> String {
+        let mut result = String::new();
+        for y in 0..field.height {
+            for x in 0..field.width {
+                result.push(if self.is_energized(x, y) { '#' } else { '.' });
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+pub struct Day16;
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    // The field with the puzzle's optics, other tables go through
+    // Field::with_optics
+    type Parsed = Field;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Field::new(input)
+    }
+
+    fn part1(field: &Self::Parsed) -> Answer {
+        let mut beams = Beams::new(field);
+        let entry = Entry { x: 0, y: 0, direction: Direction::Right };
+        field.energize(entry, &mut beams).into()
+    }
+
+    fn part2(field: &Self::Parsed) -> Answer {
+        field.max_energized_condensed().into()
+    }
+}
+
+aoc::register!(Day16);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const INPUT: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    // The straightforward version, stepping one tile at a time
+    fn energize_by_walking(field: &Field, entry: Entry) -> usize {
+        let mut seen = HashSet::new();
+        let mut stack = vec![(entry.x, entry.y, entry.direction)];
+        while let Some((x, y, direction)) = stack.pop() {
+            if !seen.insert((x, y, direction)) {
+                continue;
+            }
+            let turns = match field.optic_at[y * field.width + x] {
+                NOWHERE => PASS_THROUGH,
+                id => field.optics[id as usize].turns,
+            }[direction as usize];
+            for next_direction in Direction::ALL {
+                if turns & next_direction.bit() != 0 {
+                    if let Some((x, y)) = field.step(x, y, next_direction) {
+                        stack.push((x, y, next_direction));
+                    }
+                }
+            }
+        }
+        seen.iter().map(|(x, y, _)| (x, y)).collect::<HashSet<_>>().len()
+    }
+
+    // A deterministic field with a sprinkling of mirrors and splitters
+    fn generated(width: usize, height: usize, seed: u64) -> String {
+        generated_with("-|/\\", width, height, seed)
+    }
+
+    // Same, with each of the given tiles making up a fortieth of the field
+    fn generated_with(tiles: &str, width: usize, height: usize, seed: u64) -> String {
+        let tiles: Vec<char> = tiles.chars().collect();
+        let mut state = seed;
+        let mut field = String::with_capacity((width + 1) * height);
+        for _ in 0..height {
+            for _ in 0..width {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                field.push(*tiles.get((state >> 33) as usize % 40).unwrap_or(&'.'));
+            }
+            field.push('\n');
+        }
+        field
+    }
+
+    // The puzzle's tiles plus an absorber, a four-way splitter and a pair of
+    // one-way mirrors that only turn beams coming from one side
+    const EXTENDED: &str = r".   U   D   L   R
+-   LR  LR  L   R
+|   U   D   UD  UD
+/   R   L   D   U
+\   L   R   U   D
+#   -   -   -   -
++   UDLR UDLR UDLR UDLR
+>   U   D   R   R
+<   U   D   L   L
+";
+
+    #[test]
+    fn test_optics_default() {
+        let optics = Optics::default();
+        assert_eq!(optics.outgoing('.', Direction::Left), Some(vec![Direction::Left]));
+        assert_eq!(optics.outgoing('-', Direction::Up), Some(vec![Direction::Left, Direction::Right]));
+        assert_eq!(optics.outgoing('|', Direction::Up), Some(vec![Direction::Up]));
+        assert_eq!(optics.outgoing('/', Direction::Right), Some(vec![Direction::Up]));
+        assert_eq!(optics.outgoing('\\', Direction::Right), Some(vec![Direction::Down]));
+        assert_eq!(optics.outgoing('#', Direction::Right), None);
+        assert_eq!(optics.outgoing('é', Direction::Right), None);
+    }
+
+    #[test]
+    fn test_optics_parse() {
+        let optics = Optics::parse(EXTENDED).unwrap();
+        assert_eq!(optics.outgoing('#', Direction::Down), Some(vec![]));
+        assert_eq!(optics.outgoing('+', Direction::Down), Some(Direction::ALL.to_vec()));
+        assert_eq!(optics.outgoing('>', Direction::Left), Some(vec![Direction::Right]));
+
+        let error = |config: &str| match Optics::parse(config) {
+            Err(ConfigError::Syntax { line, .. }) => line,
+            result => panic!("expected a syntax error, got {:?}", result),
+        };
+        assert_eq!(error(". U D L"), 1);
+        assert_eq!(error("// comment\nab U D L R"), 2);
+        assert_eq!(error(". U D L R\n. U D L R"), 2);
+        assert_eq!(error(". U D L X"), 1);
+        assert_eq!(error(". U D LL R"), 1);
+        assert!(matches!(Optics::load("no such file"), Err(ConfigError::Io(_))));
+    }
+
+    #[test]
+    fn test_extended_optics() {
+        let optics = Optics::parse(EXTENDED).unwrap();
+
+        // The absorber stops the beam, the four-way splitter sends it back out
+        // the way it came as well
+        let field = Field::with_optics("...#.\n.+...\n.....", &optics).unwrap();
+        let mut beams = Beams::new(&field);
+        assert_eq!(field.energize(Entry { x: 0, y: 0, direction: Direction::Right }, &mut beams), 4);
+        assert_eq!(field.energize(Entry { x: 0, y: 1, direction: Direction::Right }, &mut beams), 7);
+        assert_eq!(beams.get_beams_string(&field), ".#...\n#####\n.#...\n");
+
+        // The one-way mirror turns beams coming from the left around but lets
+        // beams from the right through
+        let field = Field::with_optics("..<..", &optics).unwrap();
+        assert_eq!(field.energize(Entry { x: 0, y: 0, direction: Direction::Right }, &mut beams), 3);
+        assert_eq!(field.energize(Entry { x: 4, y: 0, direction: Direction::Left }, &mut beams), 5);
+
+        for (width, height, seed) in [(7, 5, 10), (65, 70, 11), (110, 110, 12)] {
+            let field = Field::with_optics(&generated_with("-|/\\#+<>", width, height, seed), &optics).unwrap();
+            let expected: Vec<usize> =
+                field.entries().into_iter().map(|entry| energize_by_walking(&field, entry)).collect();
+            assert_eq!(BeamGraph::new(&field).energize_all(), expected);
+        }
+    }
+
+    #[test]
+    fn test_unknown_tile() {
+        assert_eq!(
+            Field::new("..#..").unwrap_err(),
+            ParseError::Syntax { line: 1, message: "Unknown tile # at column 3".to_string() }
+        );
+        assert_eq!(
+            Field::with_optics(".....\n...é.", &Optics::parse(EXTENDED).unwrap()).unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Unknown tile é at column 4".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        assert_eq!(
+            Field::new("...\n\n....\n...").unwrap_err(),
+            ParseError::Syntax { line: 3, message: "Row of 4 tiles, the rows before have 3".to_string() }
+        );
+        assert_eq!(Field::new("").unwrap().width(), 0);
+    }
+
+    #[test]
+    fn test_parse() {
+        let field = Field::new(INPUT).unwrap();
+        assert_eq!(field.height(), 10);
+        assert_eq!(field.width(), 10);
+        assert_eq!(field.get_tile(1, 0), '|');
+        assert_eq!(field.get_tile(9, 5), '\\');
+    }
+
+    #[test]
+    fn test_entries() {
+        let field = Field::new(INPUT).unwrap();
+        let entries = field.entries();
+        assert_eq!(entries.len(), 40);
+        assert!(entries.contains(&Entry { x: 0, y: 0, direction: Direction::Right }));
+        assert!(entries.contains(&Entry { x: 3, y: 0, direction: Direction::Down }));
+        assert!(entries.contains(&Entry { x: 9, y: 9, direction: Direction::Up }));
+    }
+
+    #[test]
+    fn test_energize() {
+        let field = Field::new(INPUT).unwrap();
+        let mut beams = Beams::new(&field);
+        let energized = field.energize(Entry { x: 0, y: 0, direction: Direction::Right }, &mut beams);
+        assert_eq!(energized, 46);
+        assert_eq!(
+            beams.get_beams_string(&field),
+            "######....
+.#...#....
+.#...#####
+.#...##...
+.#...##...
+.#...##...
+.#..####..
+########..
+.#######..
+.#...#.#..
+"
+        );
+
+        // Reusing the beams starts over from scratch
+        let energized = field.energize(Entry { x: 3, y: 0, direction: Direction::Down }, &mut beams);
+        assert_eq!(energized, 51);
+        assert_eq!(
+            beams.get_beams_string(&field),
+            ".#####....
+.#.#.#....
+.#.#.#####
+.#.#.##...
+.#.#.##...
+.#.#.##...
+.#.#####..
+########..
+.#######..
+.#...#.#..
+"
+        );
+    }
+
+    #[test]
+    fn test_max_energized() {
+        let field = Field::new(INPUT).unwrap();
+        assert_eq!(field.max_energized(), 51);
+        #[cfg(feature = "parallel")]
+        assert_eq!(field.max_energized_parallel(), 51);
+    }
+
+    #[test]
+    fn test_energize_matches_walking() {
+        // Sizes on both sides of the 64 tile word boundaries
+        for (width, height, seed) in [(7, 5, 1), (64, 3, 2), (65, 70, 3), (130, 20, 4)] {
+            let field = Field::new(&generated(width, height, seed)).unwrap();
+            let mut beams = Beams::new(&field);
+            for entry in field.entries() {
+                assert_eq!(field.energize(entry, &mut beams), energize_by_walking(&field, entry), "{:?}", entry);
+            }
+        }
+    }
+
+    #[test]
+    fn test_condensed_matches_brute_force() {
+        let mut fields = vec![INPUT.to_string()];
+        for (width, height, seed) in [(7, 5, 5), (64, 3, 6), (65, 70, 7), (130, 20, 8), (110, 110, 9)] {
+            fields.push(generated(width, height, seed));
+        }
+        for input in fields {
+            let field = Field::new(&input).unwrap();
+            let mut beams = Beams::new(&field);
+            let expected: Vec<usize> = field.entries().into_iter().map(|entry| field.energize(entry, &mut beams)).collect();
+            assert_eq!(BeamGraph::new(&field).energize_all(), expected);
+        }
+        assert_eq!(Field::new(INPUT).unwrap().max_energized_condensed(), 51);
+    }
+
+    #[test]
+    fn test_condensed_loops() {
+        // The splitters feed each other through the mirrors, so a beam going
+        // down into the lower mirror comes back to it
+        let field = Field::new(
+            r"..........
+..|.....\.
+..........
+..\.....|.
+..........",
+        )
+        .unwrap();
+        let graph = BeamGraph::new(&field);
+        let mirror = field.optic_at[3 * field.width() + 2];
+        let component = graph.component[to_state(mirror, Direction::Down) as usize] as usize;
+        assert_eq!(graph.members[component].len(), 4);
+        assert!(graph.component_count() < field.optics.len() * 4);
+    }
+
+    #[test]
+    fn test_large_field() {
+        // Deep enough that recursing at every splitter would blow the stack
+        let field = Field::new(&generated(1000, 1000, 16)).unwrap();
+        let mut beams = Beams::new(&field);
+        let entry = Entry { x: 0, y: 0, direction: Direction::Right };
+        assert_eq!(field.energize(entry, &mut beams), energize_by_walking(&field, entry));
+        assert_eq!(field.max_energized_condensed(), 835163);
+    }
+
+    #[test]
+    fn test_solution_parse() {
+        let field = Day16::parse(INPUT).unwrap();
+        assert_eq!((field.width(), field.height()), (10, 10));
+        assert_eq!(Day16::part1(&field), Answer::UInt(46));
+
+        assert_eq!(
+            Day16::parse("..\n.x").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Unknown tile x at column 2".to_string() }
+        );
+        assert_eq!(
+            Day16::parse("..\n.").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Row of 1 tiles, the rows before have 2".to_string() }
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use day_19::{apply_workflows, parse, Decision, FlatTree, Rating, MAX_RATING, MIN_RATING};
+
+// Deterministic pseudo random ratings so runs are comparable
+fn synthetic_ratings(num_ratings: usize) -> Vec<Rating> {
+    let mut state: u64 = 0x2023_0019;
+    let mut next = || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        MIN_RATING + (state >> 33) as u32 % (MAX_RATING - MIN_RATING + 1)
+    };
+    (0..num_ratings).map(|_| Rating { x: next(), m: next(), a: next(), s: next() }).collect()
+}
+
+fn workflows(c: &mut Criterion) {
+    let (_, (workflows, _)) = parse(include_str!("../input.txt")).unwrap();
+    let decision = Decision::compile(&workflows).unwrap();
+    let tree = FlatTree::new(&decision);
+    let ratings = synthetic_ratings(1_000_000);
+
+    let mut group = c.benchmark_group("day-19 1M ratings");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(ratings.len() as u64));
+    group.bench_function("interpreter", |b| {
+        b.iter(|| ratings.iter().filter(|rating| apply_workflows(&workflows, black_box(rating))).count())
+    });
+    group.bench_function("decision tree", |b| {
+        b.iter(|| ratings.iter().filter(|rating| decision.evaluate(black_box(rating))).count())
+    });
+    group.bench_function("flat tree", |b| {
+        b.iter(|| ratings.iter().filter(|rating| tree.evaluate(black_box(rating))).count())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, workflows);
+criterion_main!(benches);
@@ -0,0 +1,1105 @@
+use nom::{
+    IResult,
+    character::complete::{
+        alpha1,
+        // digit1,
+        // space0,
+        // alphanumeric1,
+    },
+    sequence::{
+        delimited,
+        preceded,
+        terminated,
+        tuple,
+    },
+    combinator::map,
+    branch::alt,
+    multi::{
+        separated_list0,
+        // separated_list1,
+    },
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::Write;
+
+use aoc::{Answer, ParseError, Solution};
+
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Operator {
+    GreaterThan,
+    LessThan,
+    EqualTo
+}
+impl From<Operator> for std::cmp::Ordering {
+    fn from(op: Operator) -> Self {
+        match op {
+            Operator::GreaterThan => std::cmp::Ordering::Greater,
+            Operator::LessThan => std::cmp::Ordering::Less,
+            Operator::EqualTo => std::cmp::Ordering::Equal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Rule<'a> {
+    pub name: &'a str,
+    pub operator: Operator,
+    pub value: u32,
+    pub next: &'a str,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Workflow<'a> {
+    pub name: &'a str,
+    pub rules: Vec<Rule<'a>>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Rating {
+    pub x: u32,
+    pub m: u32,
+    pub a: u32,
+    pub s: u32,
+}
+impl Rating {
+    // The value for a category, in the order of CATEGORIES
+    pub fn get(&self, category: usize) -> u32 {
+        match category {
+            0 => self.x,
+            1 => self.m,
+            2 => self.a,
+            3 => self.s,
+            _ => panic!("Unknown category: {}", category),
+        }
+    }
+}
+
+use nom::character::complete;
+
+pub fn parse_rule(input: &str) -> IResult<&str, Rule<'_>> {
+    let (input, name) = alpha1(input)?;
+    let (input, operator) = alt((
+        map(complete::char('>'), |_| Operator::GreaterThan),
+        map(complete::char('<'), |_| Operator::LessThan),
+    ))(input)?;
+    let (input, value) = complete::u32(input)?;
+    let (input, _) = complete::char(':')(input)?;
+    let (input, next) = alpha1(input)?;
+
+    let rule: Rule = Rule {
+        name,
+        operator,
+        value,
+        next,
+    };
+
+    Ok((input, rule))
+}
+
+pub fn parse_rules(input: &str) -> IResult<&str, Vec<Rule<'_>>> {
+    
+    let (_, rules_list) = terminated(
+        separated_list0(
+            nom::bytes::complete::tag(","),
+            alt((
+                parse_rule,
+                map(
+                    alpha1,
+                    |fallback| {
+                        Rule {
+                            name: "fallback",
+                            operator: Operator::EqualTo,
+                            value: 0,
+                            next: fallback,
+                        }
+                    }
+                )
+            ))
+        ),
+        nom::bytes::complete::tag("}")
+    )(input)?;
+
+    Ok(("}", rules_list))
+}
+
+pub fn parse_workflow(input: &str) -> IResult<&str, Workflow<'_>> {
+    let (input, name) = alpha1(input)?;
+    let (input, rules_list) = delimited(
+        nom::bytes::complete::tag("{"),
+        parse_rules,
+        nom::bytes::complete::tag("}")
+    )(input)?;
+
+    let wf: Workflow = Workflow {
+        name,
+        rules: rules_list,
+    };
+
+    Ok((input, wf))
+}
+
+pub fn parse_rating(input: &str) -> IResult<&str, Rating> {
+    let (input, rating) = delimited(
+        nom::bytes::complete::tag("{"),
+        separated_list0(
+            nom::bytes::complete::tag(","),
+            tuple((
+                alpha1,
+                preceded(
+                    nom::bytes::complete::tag("="),
+                    complete::u32,
+                ),
+            ))
+        ),
+        nom::bytes::complete::tag("}")
+    )(input)?;
+
+    let rating: Rating = Rating {
+        x: rating[0].1,
+        m: rating[1].1,
+        a: rating[2].1,
+        s: rating[3].1,
+    }; 
+
+    Ok((input, rating))
+}
+
+pub fn parse(input: &str) -> IResult<&str, (HashMap<&str, Workflow<'_>>, Vec<Rating>)> {
+    // First parse multiple lines of Workflows, then
+    // a single empty line followed by multiple lines of Ratings
+
+    let mut workflows: HashMap<&str, Workflow> = HashMap::new();
+    let mut ratings: Vec<Rating> = Vec::new();
+
+    let mut workflows_done = false;
+    for line in input.lines() {
+        if line.is_empty() {
+            workflows_done = true;
+            continue;
+        }
+
+        if workflows_done {
+            let (_, rating) = parse_rating(line)?;
+            ratings.push(rating);
+            continue;
+        } else {
+            let (_, workflow) = parse_workflow(line)?;
+            //workflows.push(workflow);
+            workflows.insert(workflow.name, workflow);
+        }
+    }
+
+    Ok((input, (workflows, ratings)))
+}
+
+pub fn apply_workflows(workflows: &HashMap<&str, Workflow>, rating: &Rating) -> bool {
+    let mut current_workflow: &Workflow = &workflows["in"];
+
+    let mut max_ctr = 100;
+
+    loop {
+        max_ctr -= 1;
+        if max_ctr == 0 {
+            panic!("max_ctr reached!");
+        }
+
+        for rule in current_workflow.rules.iter() {
+            if rule.name == "fallback" {
+                if rule.next == "R" {
+                    return false;
+                } else if rule.next == "A" {
+                    return true;
+                } else {
+                    current_workflow = &workflows[rule.next];
+                }
+                break;
+            }
+
+            let value = match rule.name {
+                "x" => rating.x,
+                "m" => rating.m,
+                "a" => rating.a,
+                "s" => rating.s,
+                _ => panic!("Unknown rule name: {}", rule.name),
+            };
+
+            let accepted = (rule.operator == Operator::GreaterThan && value > rule.value)
+                || (rule.operator == Operator::LessThan && value < rule.value);
+
+            if accepted {
+                if rule.next == "R" {
+                    return false;
+                } else if rule.next == "A" {
+                    return true;
+                } else {
+                    current_workflow = &workflows[rule.next];
+                }
+                break;
+            }
+        }
+    }
+}
+
+// Ratings in the puzzle go from 1 to 4000 in every category
+pub const MIN_RATING: u32 = 1;
+pub const MAX_RATING: u32 = 4000;
+
+pub const START: &str = "in";
+pub const ACCEPT: &str = "A";
+pub const REJECT: &str = "R";
+
+pub const CATEGORIES: [&str; 4] = ["x", "m", "a", "s"];
+
+pub fn category_index(name: &str) -> Option<usize> {
+    CATEGORIES.iter().position(|category| *category == name)
+}
+
+pub fn is_fallback(rule: &Rule) -> bool {
+    rule.name == "fallback"
+}
+
+// Inclusive range of values, empty when start > end
+pub type Range = (u32, u32);
+
+pub fn is_empty(range: Range) -> bool {
+    range.0 > range.1
+}
+
+// Splits a range into the values that match the rule's condition and the
+// ones that don't
+pub fn split_range(range: Range, rule: &Rule) -> (Range, Range) {
+    let (start, end) = range;
+    match rule.operator {
+        // Nothing is below 0 or above u32::MAX, so those bounds match nothing
+        Operator::LessThan => match rule.value.checked_sub(1) {
+            Some(below) => ((start, end.min(below)), (start.max(rule.value), end)),
+            None => ((1, 0), range),
+        },
+        Operator::GreaterThan => match rule.value.checked_add(1) {
+            Some(above) => ((start.max(above), end), (start, end.min(rule.value))),
+            None => ((1, 0), range),
+        },
+        Operator::EqualTo => (range, (1, 0)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+// Rules are numbered from 0 within their workflow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic<'a> {
+    MissingStart,
+    UnknownCategory { workflow: &'a str, rule: usize, category: &'a str },
+    UndefinedWorkflow { workflow: &'a str, rule: usize, target: &'a str },
+    // The last rule has a condition, so ratings can fall off the end
+    MissingFallback { workflow: &'a str },
+    // No rating that gets as far as the rule can match it, the earlier rules
+    // in the workflow take all of them
+    ShadowedRule { workflow: &'a str, rule: usize },
+    Unreachable { workflow: &'a str },
+    // Workflows that can pass ratings around between them forever
+    Cycle { workflows: Vec<&'a str> },
+    // Every rating that enters the workflow ends up accepted, or every one
+    // ends up rejected
+    ConstantOutcome { workflow: &'a str, accepted: bool },
+}
+impl Diagnostic<'_> {
+    // Errors make apply_workflows panic or loop, warnings are just dead or
+    // redundant rules
+    pub fn severity(&self) -> Severity {
+        match self {
+            Diagnostic::MissingStart
+            | Diagnostic::UnknownCategory { .. }
+            | Diagnostic::UndefinedWorkflow { .. }
+            | Diagnostic::MissingFallback { .. }
+            | Diagnostic::Cycle { .. } => Severity::Error,
+            Diagnostic::ShadowedRule { .. } | Diagnostic::Unreachable { .. } | Diagnostic::ConstantOutcome { .. } => {
+                Severity::Warning
+            }
+        }
+    }
+}
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Diagnostic::MissingStart => write!(f, "there is no \"{}\" workflow to start from", START),
+            Diagnostic::UnknownCategory { workflow, rule, category } => {
+                write!(f, "{} rule {}: unknown category {}", workflow, rule, category)
+            }
+            Diagnostic::UndefinedWorkflow { workflow, rule, target } => {
+                write!(f, "{} rule {}: workflow {} is not defined", workflow, rule, target)
+            }
+            Diagnostic::MissingFallback { workflow } => write!(f, "{}: the last rule has a condition", workflow),
+            Diagnostic::ShadowedRule { workflow, rule } => {
+                write!(f, "{} rule {}: never matches, earlier rules catch every rating", workflow, rule)
+            }
+            Diagnostic::Unreachable { workflow } => write!(f, "{}: unreachable from \"{}\"", workflow, START),
+            Diagnostic::Cycle { workflows } => write!(f, "workflows {} form a cycle", workflows.join(", ")),
+            Diagnostic::ConstantOutcome { workflow, accepted } => write!(
+                f,
+                "{}: always ends up {}",
+                workflow,
+                if *accepted { "accepted" } else { "rejected" }
+            ),
+        }
+    }
+}
+
+// Outcomes a workflow can lead to, as bits
+const ACCEPTED: u8 = 1;
+const REJECTED: u8 = 2;
+
+struct Validator<'w, 'a> {
+    workflows: &'w HashMap<&'a str, Workflow<'a>>,
+    // Workflow names in sorted order, so that diagnostics come out the same
+    // way every time
+    names: Vec<&'a str>,
+    // Rules that some rating can match, per workflow
+    live: HashMap<&'a str, Vec<bool>>,
+}
+impl<'a> Validator<'_, 'a> {
+    // Targets of the rules that can match, which exist as workflows
+    fn edges(&self, name: &str) -> Vec<&'a str> {
+        let workflow = &self.workflows[name];
+        workflow
+            .rules
+            .iter()
+            .zip(&self.live[name])
+            .filter(|(rule, live)| **live && self.workflows.contains_key(rule.next))
+            .map(|(rule, _)| rule.next)
+            .collect()
+    }
+
+    fn check_rules(&mut self, diagnostics: &mut Vec<Diagnostic<'a>>) {
+        for name in self.names.clone() {
+            let workflow = &self.workflows[name];
+            let mut remaining: [Range; 4] = [(MIN_RATING, MAX_RATING); 4];
+            let mut live = Vec::new();
+            for (i, rule) in workflow.rules.iter().enumerate() {
+                if rule.next != ACCEPT && rule.next != REJECT && !self.workflows.contains_key(rule.next) {
+                    diagnostics.push(Diagnostic::UndefinedWorkflow { workflow: name, rule: i, target: rule.next });
+                }
+
+                let matches = if remaining.iter().any(|range| is_empty(*range)) {
+                    false
+                } else if is_fallback(rule) {
+                    remaining = [(1, 0); 4];
+                    true
+                } else if let Some(category) = category_index(rule.name) {
+                    let (matching, rest) = split_range(remaining[category], rule);
+                    remaining[category] = rest;
+                    !is_empty(matching)
+                } else {
+                    diagnostics.push(Diagnostic::UnknownCategory { workflow: name, rule: i, category: rule.name });
+                    true
+                };
+                if !matches {
+                    diagnostics.push(Diagnostic::ShadowedRule { workflow: name, rule: i });
+                }
+                live.push(matches);
+            }
+            if !workflow.rules.last().is_some_and(is_fallback) {
+                diagnostics.push(Diagnostic::MissingFallback { workflow: name });
+            }
+            self.live.insert(name, live);
+        }
+    }
+
+    fn check_reachable(&self, diagnostics: &mut Vec<Diagnostic<'a>>) {
+        let mut seen = HashSet::from([START]);
+        let mut queue = vec![START];
+        while let Some(name) = queue.pop() {
+            for next in self.edges(name) {
+                if seen.insert(next) {
+                    queue.push(next);
+                }
+            }
+        }
+        for name in &self.names {
+            if !seen.contains(name) {
+                diagnostics.push(Diagnostic::Unreachable { workflow: name });
+            }
+        }
+    }
+
+    // Tarjan's algorithm over the workflows. Any component with more than one
+    // workflow, or a workflow that sends ratings to itself, is a cycle.
+    fn check_cycles(&self, diagnostics: &mut Vec<Diagnostic<'a>>) {
+        struct Tarjan<'a> {
+            index: HashMap<&'a str, usize>,
+            lowlink: HashMap<&'a str, usize>,
+            stack: Vec<&'a str>,
+            on_stack: HashSet<&'a str>,
+            components: Vec<Vec<&'a str>>,
+        }
+
+        fn connect<'a>(validator: &Validator<'_, 'a>, tarjan: &mut Tarjan<'a>, name: &'a str) {
+            let index = tarjan.index.len();
+            tarjan.index.insert(name, index);
+            tarjan.lowlink.insert(name, index);
+            tarjan.stack.push(name);
+            tarjan.on_stack.insert(name);
+
+            for next in validator.edges(name) {
+                if !tarjan.index.contains_key(next) {
+                    connect(validator, tarjan, next);
+                    let low = tarjan.lowlink[name].min(tarjan.lowlink[next]);
+                    tarjan.lowlink.insert(name, low);
+                } else if tarjan.on_stack.contains(next) {
+                    let low = tarjan.lowlink[name].min(tarjan.index[next]);
+                    tarjan.lowlink.insert(name, low);
+                }
+            }
+
+            if tarjan.lowlink[name] == tarjan.index[name] {
+                let mut component = Vec::new();
+                loop {
+                    let member = tarjan.stack.pop().unwrap();
+                    tarjan.on_stack.remove(member);
+                    component.push(member);
+                    if member == name {
+                        break;
+                    }
+                }
+                tarjan.components.push(component);
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            index: HashMap::new(),
+            lowlink: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+        for name in &self.names {
+            if !tarjan.index.contains_key(name) {
+                connect(self, &mut tarjan, name);
+            }
+        }
+
+        let mut cycles: Vec<Vec<&str>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| component.len() > 1 || self.edges(component[0]).contains(&component[0]))
+            .collect();
+        for cycle in cycles.iter_mut() {
+            cycle.sort_unstable();
+        }
+        cycles.sort_unstable();
+        diagnostics.extend(cycles.into_iter().map(|workflows| Diagnostic::Cycle { workflows }));
+    }
+
+    // Outcomes a rating entering the workflow can end up with, or None if
+    // that can't be told because the workflow leads into a cycle or to an
+    // undefined workflow
+    fn outcomes(&self, name: &'a str, memo: &mut HashMap<&'a str, Option<u8>>) -> Option<u8> {
+        if let Some(outcomes) = memo.get(name) {
+            return *outcomes;
+        }
+        // Anything that comes back here while it's being worked out is part
+        // of a cycle
+        memo.insert(name, None);
+
+        let rules = &self.workflows[name].rules;
+        // Ratings that fall off the end go nowhere in particular
+        let mut outcomes = rules.last().is_some_and(is_fallback).then_some(0);
+        for (rule, live) in rules.iter().zip(&self.live[name]) {
+            if !live {
+                continue;
+            }
+            let next = match rule.next {
+                ACCEPT => Some(ACCEPTED),
+                REJECT => Some(REJECTED),
+                next if self.workflows.contains_key(next) => self.outcomes(next, memo),
+                _ => None,
+            };
+            outcomes = outcomes.zip(next).map(|(outcomes, next)| outcomes | next);
+        }
+
+        memo.insert(name, outcomes);
+        outcomes
+    }
+
+    fn check_outcomes(&self, diagnostics: &mut Vec<Diagnostic<'a>>) {
+        let mut memo = HashMap::new();
+        for name in &self.names {
+            match self.outcomes(name, &mut memo) {
+                Some(ACCEPTED) => diagnostics.push(Diagnostic::ConstantOutcome { workflow: name, accepted: true }),
+                Some(REJECTED) => diagnostics.push(Diagnostic::ConstantOutcome { workflow: name, accepted: false }),
+                _ => {}
+            }
+        }
+    }
+}
+
+// Checks the workflows for anything that would trip up apply_workflows, as
+// well as rules and workflows that make no difference. The analysis looks at
+// one workflow at a time, so a rule only counts as shadowed by earlier rules
+// in its own workflow.
+pub fn validate<'a>(workflows: &HashMap<&'a str, Workflow<'a>>) -> Vec<Diagnostic<'a>> {
+    let mut names: Vec<&str> = workflows.keys().copied().collect();
+    names.sort_unstable();
+    let mut validator = Validator { workflows, names, live: HashMap::new() };
+
+    let mut diagnostics = Vec::new();
+    let has_start = workflows.contains_key(START);
+    if !has_start {
+        diagnostics.push(Diagnostic::MissingStart);
+    }
+    validator.check_rules(&mut diagnostics);
+    if has_start {
+        validator.check_reachable(&mut diagnostics);
+    }
+    validator.check_cycles(&mut diagnostics);
+    validator.check_outcomes(&mut diagnostics);
+
+    diagnostics
+}
+
+// A decision tree with every workflow inlined, as nested comparisons of a
+// single category against a threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Accept,
+    Reject,
+    // Ratings with the category below the threshold go one way, the rest the
+    // other
+    Split { category: usize, threshold: u32, below: Box<Decision>, above: Box<Decision> },
+}
+impl Decision {
+    // Inlines the workflows starting from "in". Comparisons that every rating
+    // reaching them passes or fails are dropped, as are splits that end up
+    // the same either way.
+    pub fn compile<'a>(workflows: &HashMap<&'a str, Workflow<'a>>) -> Result<Self, Vec<Diagnostic<'a>>> {
+        let errors: Vec<Diagnostic> = validate(workflows)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity() == Severity::Error)
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self::compile_target(workflows, START, [(MIN_RATING, MAX_RATING); 4]))
+    }
+
+    fn compile_target(workflows: &HashMap<&str, Workflow>, target: &str, ranges: [Range; 4]) -> Self {
+        match target {
+            ACCEPT => Decision::Accept,
+            REJECT => Decision::Reject,
+            _ => Self::compile_rules(workflows, &workflows[target].rules, ranges),
+        }
+    }
+
+    fn compile_rules(workflows: &HashMap<&str, Workflow>, rules: &[Rule], mut ranges: [Range; 4]) -> Self {
+        let rule = &rules[0];
+        let Some(category) = category_index(rule.name) else {
+            return Self::compile_target(workflows, rule.next, ranges);
+        };
+
+        let (matching, rest) = split_range(ranges[category], rule);
+        if is_empty(matching) {
+            return Self::compile_rules(workflows, &rules[1..], ranges);
+        }
+        ranges[category] = matching;
+        let matched = Self::compile_target(workflows, rule.next, ranges);
+        if is_empty(rest) {
+            return matched;
+        }
+        ranges[category] = rest;
+        let unmatched = Self::compile_rules(workflows, &rules[1..], ranges);
+
+        // Both sides are non-empty, so the split is where the upper one starts
+        let (threshold, below, above) = match rule.operator {
+            Operator::LessThan => (rest.0, matched, unmatched),
+            _ => (matching.0, unmatched, matched),
+        };
+        if below == above {
+            return below;
+        }
+        Decision::Split { category, threshold, below: Box::new(below), above: Box::new(above) }
+    }
+
+    pub fn evaluate(&self, rating: &Rating) -> bool {
+        let mut decision = self;
+        loop {
+            match decision {
+                Decision::Accept => return true,
+                Decision::Reject => return false,
+                Decision::Split { category, threshold, below, above } => {
+                    decision = if rating.get(*category) < *threshold { below } else { above };
+                }
+            }
+        }
+    }
+
+    pub fn splits(&self) -> usize {
+        match self {
+            Decision::Split { below, above, .. } => 1 + below.splits() + above.splits(),
+            _ => 0,
+        }
+    }
+}
+
+// Leaves in a FlatTree are stored as these in place of a node index
+const ACCEPT_NODE: u32 = u32::MAX;
+const REJECT_NODE: u32 = u32::MAX - 1;
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    category: u32,
+    threshold: u32,
+    below: u32,
+    above: u32,
+}
+
+// A Decision laid out in one array, which is what to use for evaluating lots
+// of ratings
+#[derive(Debug, Clone)]
+pub struct FlatTree {
+    nodes: Vec<Node>,
+    root: u32,
+}
+impl FlatTree {
+    pub fn new(decision: &Decision) -> Self {
+        let mut nodes = Vec::new();
+        let root = Self::add(&mut nodes, decision);
+        Self { nodes, root }
+    }
+
+    fn add(nodes: &mut Vec<Node>, decision: &Decision) -> u32 {
+        match decision {
+            Decision::Accept => ACCEPT_NODE,
+            Decision::Reject => REJECT_NODE,
+            Decision::Split { category, threshold, below, above } => {
+                let index = nodes.len();
+                nodes.push(Node { category: *category as u32, threshold: *threshold, below: 0, above: 0 });
+                nodes[index].below = Self::add(nodes, below);
+                nodes[index].above = Self::add(nodes, above);
+                index as u32
+            }
+        }
+    }
+
+    pub fn evaluate(&self, rating: &Rating) -> bool {
+        let values = [rating.x, rating.m, rating.a, rating.s];
+        let mut current = self.root;
+        while current < REJECT_NODE {
+            let node = &self.nodes[current as usize];
+            current = if values[node.category as usize] < node.threshold { node.below } else { node.above };
+        }
+        current == ACCEPT_NODE
+    }
+}
+
+// The condition of a rule as written in the input, or "else" for the fallback
+pub fn condition(rule: &Rule) -> String {
+    match rule.operator {
+        Operator::LessThan => format!("{}<{}", rule.name, rule.value),
+        Operator::GreaterThan => format!("{}>{}", rule.name, rule.value),
+        Operator::EqualTo => "else".to_string(),
+    }
+}
+
+// The workflows as a Graphviz graph, with an edge per rule labelled with its
+// condition. The start workflow is blue, and A and R are green and red.
+pub fn to_dot(workflows: &HashMap<&str, Workflow>) -> String {
+    let mut names: Vec<&str> = workflows.keys().copied().collect();
+    names.sort_unstable();
+
+    let mut dot = String::from("digraph workflows {\n    node [shape=box];\n");
+    writeln!(dot, "    \"{}\" [style=filled, fillcolor=lightblue];", START).unwrap();
+    writeln!(dot, "    \"{}\" [shape=doublecircle, style=filled, fillcolor=palegreen];", ACCEPT).unwrap();
+    writeln!(dot, "    \"{}\" [shape=doublecircle, style=filled, fillcolor=lightcoral];", REJECT).unwrap();
+    for name in &names {
+        for rule in &workflows[name].rules {
+            writeln!(dot, "    \"{}\" -> \"{}\" [label=\"{}\"];", name, rule.next, condition(rule)).unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+pub struct Day19;
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    // The workflows borrow from the input, so they're compiled right away
+    type Parsed = (FlatTree, Vec<Rating>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let (_, (workflows, ratings)) = parse(input).map_err(|error| ParseError::Invalid(error.to_string()))?;
+        let decision = Decision::compile(&workflows).map_err(|errors| {
+            ParseError::Invalid(errors.iter().map(|error| error.to_string()).collect::<Vec<_>>().join("\n"))
+        })?;
+        Ok((FlatTree::new(&decision), ratings))
+    }
+
+    fn part1((tree, ratings): &Self::Parsed) -> Answer {
+        let mut sum = 0;
+        for rating in ratings {
+            if tree.evaluate(rating) {
+                sum += rating.x + rating.m + rating.a + rating.s;
+            }
+        }
+        sum.into()
+    }
+
+    fn part2(_: &Self::Parsed) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+aoc::register!(Day19);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn test_parse_workflow() {
+        let result = parse_workflow("px{a<2006:qkq,m>2090:A,rfg}");
+        dbg!(&result);
+        assert!(result.is_ok());
+        let (_, rule) = result.unwrap();
+        assert_eq!(rule.name, "px");
+    }
+
+    #[test]
+    fn test_parse_rating() {
+        let result = parse_rating("{x=787,m=2655,a=1222,s=2876}");
+        dbg!(&result);
+        assert!(result.is_ok());
+        let (_, rating) = result.unwrap();
+        assert_eq!(rating.x, 787);
+        assert_eq!(rating.m, 2655);
+        assert_eq!(rating.a, 1222);
+        assert_eq!(rating.s, 2876);
+    }
+
+    #[test]
+    fn test_parse_everything() {
+        let result = parse(INPUT);
+        dbg!(&result);
+        assert!(result.is_ok());
+        let (_, (workflows, ratings)) = result.unwrap();
+        assert_eq!(workflows.len(), 11);
+        assert_eq!(ratings.len(), 5);
+
+        // 0 -> "px{a<2006:qkq,m>2090:A,rfg}"
+        assert_eq!(workflows["px"].name, "px");
+        assert_eq!(workflows["px"].rules.len(), 3);
+        assert_eq!(workflows["px"].rules[0].name, "a");
+        assert_eq!(workflows["px"].rules[0].operator, Operator::LessThan);
+        assert_eq!(workflows["px"].rules[0].value, 2006);
+        assert_eq!(workflows["px"].rules[0].next, "qkq");
+        assert_eq!(workflows["px"].rules[1].name, "m");
+        assert_eq!(workflows["px"].rules[1].operator, Operator::GreaterThan);
+        assert_eq!(workflows["px"].rules[1].value, 2090);
+        assert_eq!(workflows["px"].rules[1].next, "A");
+        assert_eq!(workflows["px"].rules[2].name, "fallback");
+        assert_eq!(workflows["px"].rules[2].operator, Operator::EqualTo);
+        assert_eq!(workflows["px"].rules[2].value, 0);
+        assert_eq!(workflows["px"].rules[2].next, "rfg");
+
+        // 10 -> "hdj{m>838:A,pv}"
+        assert_eq!(workflows["hdj"].name, "hdj");
+        assert_eq!(workflows["hdj"].rules.len(), 2);
+        assert_eq!(workflows["hdj"].rules[0].name, "m");
+        assert_eq!(workflows["hdj"].rules[0].operator, Operator::GreaterThan);
+        assert_eq!(workflows["hdj"].rules[0].value, 838);
+        assert_eq!(workflows["hdj"].rules[0].next, "A");
+        assert_eq!(workflows["hdj"].rules[1].name, "fallback");
+        assert_eq!(workflows["hdj"].rules[1].operator, Operator::EqualTo);
+        assert_eq!(workflows["hdj"].rules[1].value, 0);
+        assert_eq!(workflows["hdj"].rules[1].next, "pv");
+
+        // 4 -> {x=2127,m=1623,a=2188,s=1013}
+        assert_eq!(ratings[4].x, 2127);
+        assert_eq!(ratings[4].m, 1623);
+        assert_eq!(ratings[4].a, 2188);
+        assert_eq!(ratings[4].s, 1013);
+    }
+
+    fn test_parse_setup() -> (HashMap<&'static str, Workflow<'static>>, Vec<Rating>) {
+        let result = parse(INPUT);
+        assert!(result.is_ok());
+        let (_, (workflows, ratings)) = result.unwrap();
+        assert_eq!(workflows.len(), 11);
+        assert_eq!(ratings.len(), 5);
+
+        (workflows, ratings)
+    }
+
+    #[test]
+    fn test_apply_workflows_rat0() {
+        let (workflows, ratings) = test_parse_setup();
+        assert!(apply_workflows(&workflows, &ratings[0]));
+    }
+
+    #[test]
+    fn test_apply_workflows_rat1() {
+        let (workflows, ratings) = test_parse_setup();
+        assert!(!apply_workflows(&workflows, &ratings[1]));
+    }
+
+    #[test]
+    fn test_apply_workflows_rat2() {
+        let (workflows, ratings) = test_parse_setup();
+        assert!(apply_workflows(&workflows, &ratings[2]));
+    }
+
+    #[test]
+    fn test_apply_workflows_rat3() {
+        let (workflows, ratings) = test_parse_setup();
+        assert!(!apply_workflows(&workflows, &ratings[3]));
+    }
+
+    #[test]
+    fn test_apply_workflows_rat4() {
+        let (workflows, ratings) = test_parse_setup();
+        assert!(apply_workflows(&workflows, &ratings[4]));
+    }
+
+    #[test]
+    fn test_calculate_sum() {
+        let mut sum = 0;
+        let (workflows, ratings) = test_parse_setup();
+
+        for rating in ratings {
+            if apply_workflows(&workflows, &rating) {
+                sum += rating.x + rating.m + rating.a + rating.s;
+            }
+        }
+
+        assert_eq!(sum, 19114);
+    }
+
+    fn validate_input(input: &str) -> Vec<Diagnostic<'_>> {
+        let (_, (workflows, _)) = parse(input).unwrap();
+        validate(&workflows)
+    }
+
+    #[test]
+    fn test_validate_example() {
+        let (workflows, _) = test_parse_setup();
+        assert_eq!(validate(&workflows), vec![
+            Diagnostic::ConstantOutcome { workflow: "gd", accepted: false },
+            Diagnostic::ConstantOutcome { workflow: "lnx", accepted: true },
+            // Sends everything either to A or on to lnx
+            Diagnostic::ConstantOutcome { workflow: "qs", accepted: true },
+        ]);
+    }
+
+    #[test]
+    fn test_validate_missing_start() {
+        let diagnostics = validate_input("px{a<2006:A,R}\n\n");
+        assert_eq!(diagnostics[0], Diagnostic::MissingStart);
+        assert_eq!(diagnostics[0].severity(), Severity::Error);
+        // Nothing is unreachable when there's nowhere to start from
+        assert!(!diagnostics.iter().any(|diagnostic| matches!(diagnostic, Diagnostic::Unreachable { .. })));
+    }
+
+    #[test]
+    fn test_validate_references() {
+        let diagnostics = validate_input("in{a<2006:px,q>5:A,R}\npx{x<10:nope,R}\nlost{s>1:A,R}\n\n");
+        assert_eq!(diagnostics, vec![
+            Diagnostic::UnknownCategory { workflow: "in", rule: 1, category: "q" },
+            Diagnostic::UndefinedWorkflow { workflow: "px", rule: 0, target: "nope" },
+            Diagnostic::Unreachable { workflow: "lost" },
+        ]);
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.to_string().contains(':')));
+    }
+
+    #[test]
+    fn test_validate_cycles() {
+        let diagnostics = validate_input("in{x<10:b,a}\na{m>5:b,R}\nb{s<100:a,A}\nc{x>1:c,R}\n\n");
+        assert_eq!(diagnostics, vec![
+            Diagnostic::Unreachable { workflow: "c" },
+            Diagnostic::Cycle { workflows: vec!["a", "b"] },
+            Diagnostic::Cycle { workflows: vec!["c"] },
+        ]);
+        assert_eq!(diagnostics[1].to_string(), "workflows a, b form a cycle");
+        assert_eq!(diagnostics[1].severity(), Severity::Error);
+    }
+
+    #[test]
+    fn test_validate_shadowed() {
+        // x<10 covers x<5, x>4000 can't happen, and nothing gets past s>0
+        let diagnostics = validate_input("in{x<10:A,x<5:R,x>4000:R,m<3:R,s>0:A,R}\n\n");
+        assert_eq!(diagnostics, vec![
+            Diagnostic::ShadowedRule { workflow: "in", rule: 1 },
+            Diagnostic::ShadowedRule { workflow: "in", rule: 2 },
+            Diagnostic::ShadowedRule { workflow: "in", rule: 5 },
+        ]);
+        assert_eq!(diagnostics[0].severity(), Severity::Warning);
+
+        // A shadowed rule doesn't make its target reachable
+        let diagnostics = validate_input("in{x<10:A,x<5:px,R}\npx{a<5:A,R}\n\n");
+        assert_eq!(diagnostics, vec![
+            Diagnostic::ShadowedRule { workflow: "in", rule: 1 },
+            Diagnostic::Unreachable { workflow: "px" },
+        ]);
+    }
+
+    #[test]
+    fn test_split_range() {
+        let less = Rule { name: "x", operator: Operator::LessThan, value: 100, next: "A" };
+        let greater = Rule { name: "x", operator: Operator::GreaterThan, value: 100, next: "A" };
+        assert_eq!(split_range((1, 4000), &less), ((1, 99), (100, 4000)));
+        assert_eq!(split_range((1, 4000), &greater), ((101, 4000), (1, 100)));
+        assert!(is_empty(split_range((100, 200), &less).0));
+        assert!(is_empty(split_range((1, 100), &greater).0));
+
+        let never_less = Rule { name: "x", operator: Operator::LessThan, value: 0, next: "A" };
+        let never_greater = Rule { name: "x", operator: Operator::GreaterThan, value: u32::MAX, next: "A" };
+        let everything = (0, u32::MAX);
+        assert!(is_empty(split_range(everything, &never_less).0));
+        assert_eq!(split_range(everything, &never_less).1, everything);
+        assert!(is_empty(split_range(everything, &never_greater).0));
+        assert_eq!(split_range(everything, &never_greater).1, everything);
+    }
+
+    #[test]
+    fn test_validate_missing_fallback() {
+        let diagnostics = validate_input("in{a<2006:A}\n\n");
+        assert_eq!(diagnostics, vec![Diagnostic::MissingFallback { workflow: "in" }]);
+    }
+
+    // Ratings spread over the whole range, from a fixed seed
+    fn random_ratings(count: usize) -> Vec<Rating> {
+        let mut state: u64 = 19;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            MIN_RATING + (state >> 33) as u32 % (MAX_RATING - MIN_RATING + 1)
+        };
+        (0..count).map(|_| Rating { x: next(), m: next(), a: next(), s: next() }).collect()
+    }
+
+    #[test]
+    fn test_compile_simple() {
+        let (_, (workflows, _)) = parse("in{x<10:A,x<5:R,m>100:px,R}\npx{a<5:R,R}\n\n").unwrap();
+        // x<5 can't match after x<10, and px rejects everything
+        assert_eq!(Decision::compile(&workflows), Ok(Decision::Split {
+            category: 0,
+            threshold: 10,
+            below: Box::new(Decision::Accept),
+            above: Box::new(Decision::Reject),
+        }));
+
+        let (_, (workflows, _)) = parse("in{s>2000:A,lnx}\nlnx{m>1548:A,A}\n\n").unwrap();
+        assert_eq!(Decision::compile(&workflows), Ok(Decision::Accept));
+
+        // Thresholds at the very ends of u32 don't overflow
+        let (_, (workflows, _)) = parse("in{x>4294967295:R,x<0:R,m>3999:A,R}\n\n").unwrap();
+        assert_eq!(Decision::compile(&workflows), Ok(Decision::Split {
+            category: 1,
+            threshold: 4000,
+            below: Box::new(Decision::Reject),
+            above: Box::new(Decision::Accept),
+        }));
+
+        let (_, (workflows, _)) = parse("in{s>2000:in,R}\n\n").unwrap();
+        assert_eq!(Decision::compile(&workflows), Err(vec![Diagnostic::Cycle { workflows: vec!["in"] }]));
+    }
+
+    #[test]
+    fn test_compile_example() {
+        let (workflows, ratings) = test_parse_setup();
+        let decision = Decision::compile(&workflows).unwrap();
+        let flat = FlatTree::new(&decision);
+        for rating in ratings.iter().chain(&random_ratings(10_000)) {
+            let expected = apply_workflows(&workflows, rating);
+            assert_eq!(decision.evaluate(rating), expected, "{:?}", rating);
+            assert_eq!(flat.evaluate(rating), expected, "{:?}", rating);
+        }
+    }
+
+    #[test]
+    fn test_compile_input() {
+        let (_, (workflows, ratings)) = parse(include_str!("../input.txt")).unwrap();
+        let decision = Decision::compile(&workflows).unwrap();
+        let rules: usize = workflows.values().map(|workflow| workflow.rules.len() - 1).sum();
+        assert!(decision.splits() <= rules);
+        let flat = FlatTree::new(&decision);
+        for rating in ratings.iter().chain(&random_ratings(10_000)) {
+            assert_eq!(flat.evaluate(rating), apply_workflows(&workflows, rating), "{:?}", rating);
+        }
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let (workflows, _) = test_parse_setup();
+        assert_eq!(
+            to_dot(&workflows),
+            r#"digraph workflows {
+    node [shape=box];
+    "in" [style=filled, fillcolor=lightblue];
+    "A" [shape=doublecircle, style=filled, fillcolor=palegreen];
+    "R" [shape=doublecircle, style=filled, fillcolor=lightcoral];
+    "crn" -> "A" [label="x>2662"];
+    "crn" -> "R" [label="else"];
+    "gd" -> "R" [label="a>3333"];
+    "gd" -> "R" [label="else"];
+    "hdj" -> "A" [label="m>838"];
+    "hdj" -> "pv" [label="else"];
+    "in" -> "px" [label="s<1351"];
+    "in" -> "qqz" [label="else"];
+    "lnx" -> "A" [label="m>1548"];
+    "lnx" -> "A" [label="else"];
+    "pv" -> "R" [label="a>1716"];
+    "pv" -> "A" [label="else"];
+    "px" -> "qkq" [label="a<2006"];
+    "px" -> "A" [label="m>2090"];
+    "px" -> "rfg" [label="else"];
+    "qkq" -> "A" [label="x<1416"];
+    "qkq" -> "crn" [label="else"];
+    "qqz" -> "qs" [label="s>2770"];
+    "qqz" -> "hdj" [label="m<1801"];
+    "qqz" -> "R" [label="else"];
+    "qs" -> "A" [label="s>3448"];
+    "qs" -> "lnx" [label="else"];
+    "rfg" -> "gd" [label="s<537"];
+    "rfg" -> "R" [label="x>2440"];
+    "rfg" -> "A" [label="else"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_solution_parse() {
+        let parsed = Day19::parse(INPUT).unwrap();
+        assert_eq!(parsed.1.len(), 5);
+        assert_eq!(Day19::part1(&parsed), Answer::UInt(19114));
+
+        assert_eq!(
+            Day19::parse("px{a<2006:A,R}\n\n{x=1,m=2,a=3,s=4}").unwrap_err(),
+            ParseError::Invalid("there is no \"in\" workflow to start from".to_string())
+        );
+        assert!(Day19::parse("in{a<2006:A,R\n").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day_20::{ModuleId, System};
+
+// The simulator as it was before names were interned, with modules looked up
+// by name and every pulse carrying its own copies of them, to measure the
+// interned one against
+mod reference {
+    use std::collections::HashMap;
+
+    use day_20::{parse, Module, ModuleType};
+
+    pub struct Pulse {
+        pub source: String,
+        pub target: String,
+        pub value: bool,
+    }
+
+    struct State {
+        module: Module,
+        output: bool,
+    }
+
+    pub struct System {
+        modules: HashMap<String, State>,
+        pub count_high: usize,
+        pub count_low: usize,
+    }
+    impl System {
+        pub fn new(input: &str) -> Self {
+            let modules = parse(input)
+                .into_iter()
+                .map(|(name, module)| (name, State { module, output: false }))
+                .collect();
+            Self { modules, count_high: 0, count_low: 0 }
+        }
+
+        pub fn feeder(&self, target: &str) -> String {
+            self.modules.values().find(|state| state.module.targets.iter().any(|t| t == target)).unwrap().module.name.clone()
+        }
+
+        pub fn inputs(&self, name: &str) -> usize {
+            self.modules[name].module.inputs.len()
+        }
+
+        pub fn push_button_with(&mut self, mut observe: impl FnMut(&Pulse)) {
+            let mut queue = vec![Pulse { source: "button".to_string(), target: "broadcaster".to_string(), value: false }];
+            while !queue.is_empty() {
+                let mut new_queue = Vec::new();
+                for pulse in queue.iter() {
+                    if pulse.value {
+                        self.count_high += 1;
+                    } else {
+                        self.count_low += 1;
+                    }
+                    observe(pulse);
+
+                    let state = self.modules.get_mut(&pulse.target).unwrap();
+                    *state.module.inputs.get_mut(&pulse.source).unwrap() = pulse.value;
+                    match state.module.module_type {
+                        ModuleType::Flipflop => {
+                            if pulse.value {
+                                continue;
+                            }
+                            state.output = !state.output;
+                        },
+                        ModuleType::Conjunction => state.output = state.module.inputs.values().any(|value| !value),
+                        ModuleType::Button => state.output = false,
+                        ModuleType::Broadcast | ModuleType::Placeholder => state.output = pulse.value,
+                    }
+                    for target in state.module.targets.iter() {
+                        new_queue.push(Pulse { source: state.module.name.clone(), target: target.clone(), value: state.output });
+                    }
+                }
+                queue = new_queue;
+            }
+        }
+    }
+}
+
+fn reference_part1(input: &str) -> usize {
+    let mut system = reference::System::new(input);
+    for _ in 0..1000 {
+        system.push_button_with(|_| {});
+    }
+    system.count_high * system.count_low
+}
+
+fn reference_cycles(input: &str) -> Vec<usize> {
+    let mut system = reference::System::new(input);
+    let feeder = system.feeder("rx");
+    let num_inputs = system.inputs(&feeder);
+    let mut cycles: Vec<(String, usize)> = Vec::new();
+    let mut presses = 0;
+    while cycles.len() < num_inputs {
+        presses += 1;
+        system.push_button_with(|pulse| {
+            if pulse.value && pulse.target == feeder && !cycles.iter().any(|(source, _)| *source == pulse.source) {
+                cycles.push((pulse.source.clone(), presses));
+            }
+        });
+    }
+    cycles.into_iter().map(|(_, presses)| presses).collect()
+}
+
+fn part1(input: &str) -> usize {
+    let mut system = System::new(input);
+    for _ in 0..1000 {
+        system.push_button();
+    }
+    system.count_high * system.count_low
+}
+
+fn cycles(input: &str) -> Vec<usize> {
+    let mut system = System::new(input);
+    let rx = system.id("rx").unwrap();
+    let feeder = system.gates.iter().position(|gate| gate.targets.contains(&rx)).unwrap();
+    system.first_high_presses(feeder as ModuleId)
+}
+
+fn simulator(c: &mut Criterion) {
+    let input = include_str!("../input.txt");
+    assert_eq!(part1(input), reference_part1(input));
+    let mut expected = reference_cycles(input);
+    let mut actual = cycles(input);
+    expected.sort_unstable();
+    actual.sort_unstable();
+    assert_eq!(actual, expected);
+
+    let mut group = c.benchmark_group("day-20 part 1");
+    group.bench_function("reference", |b| b.iter(|| reference_part1(black_box(input))));
+    group.bench_function("interned", |b| b.iter(|| part1(black_box(input))));
+    group.finish();
+
+    let mut group = c.benchmark_group("day-20 cycle search");
+    group.sample_size(10);
+    group.bench_function("reference", |b| b.iter(|| reference_cycles(black_box(input))));
+    group.bench_function("interned", |b| b.iter(|| cycles(black_box(input))));
+    group.finish();
+}
+
+criterion_group!(benches, simulator);
+criterion_main!(benches);
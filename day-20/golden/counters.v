@@ -0,0 +1,132 @@
+module circuit #(
+    parameter QUEUE_BITS = 8
+) (
+    input wire clk,
+    input wire reset,
+    input wire button,
+    output wire rx,
+    output wire busy
+);
+    localparam QUEUE_MASK = (1 << QUEUE_BITS) - 1;
+
+    localparam [3:0] button_to_broadcaster = 4'd0;
+    localparam [3:0] aa_to_ab = 4'd1;
+    localparam [3:0] aa_to_ka = 4'd2;
+    localparam [3:0] ab_to_ka = 4'd3;
+    localparam [3:0] ba_to_kb = 4'd4;
+    localparam [3:0] broadcaster_to_aa = 4'd5;
+    localparam [3:0] broadcaster_to_ba = 4'd6;
+    localparam [3:0] fa_to_hub = 4'd7;
+    localparam [3:0] fb_to_hub = 4'd8;
+    localparam [3:0] hub_to_rx = 4'd9;
+    localparam [3:0] ka_to_fa = 4'd10;
+    localparam [3:0] kb_to_fb = 4'd11;
+
+    reg on_aa;
+    reg on_ab;
+    reg on_ba;
+    reg [0:0] mem_fa;
+    reg [0:0] mem_fb;
+    reg [1:0] mem_hub;
+    reg [1:0] mem_ka;
+    reg [0:0] mem_kb;
+    reg [4:0] queue [0:QUEUE_MASK];
+    reg [QUEUE_BITS-1:0] head;
+    reg [QUEUE_BITS-1:0] tail;
+    reg sent;
+
+    wire [3:0] pulse_wire = queue[head][4:1];
+    wire pulse_value = queue[head][0];
+    assign busy = head != tail;
+    assign rx = !(busy && !pulse_value && (pulse_wire == hub_to_rx));
+
+    always @(posedge clk) begin
+        if (reset) begin
+            head <= 0;
+            tail <= 0;
+            on_aa <= 1'b0;
+            on_ab <= 1'b0;
+            on_ba <= 1'b0;
+            mem_fa <= 0;
+            mem_fb <= 0;
+            mem_hub <= 0;
+            mem_ka <= 0;
+            mem_kb <= 0;
+        end else if (busy) begin
+            head <= head + 1;
+            case (pulse_wire)
+                broadcaster_to_aa: if (!pulse_value) begin
+                    sent = !on_aa;
+                    on_aa <= sent;
+                    queue[tail] <= {aa_to_ab, sent};
+                    queue[(tail + 1) & QUEUE_MASK] <= {aa_to_ka, sent};
+                    tail <= tail + 2;
+                end
+                aa_to_ab: if (!pulse_value) begin
+                    sent = !on_ab;
+                    on_ab <= sent;
+                    queue[tail] <= {ab_to_ka, sent};
+                    tail <= tail + 1;
+                end
+                broadcaster_to_ba: if (!pulse_value) begin
+                    sent = !on_ba;
+                    on_ba <= sent;
+                    queue[tail] <= {ba_to_kb, sent};
+                    tail <= tail + 1;
+                end
+                button_to_broadcaster: begin
+                    sent = pulse_value;
+                    queue[tail] <= {broadcaster_to_aa, sent};
+                    queue[(tail + 1) & QUEUE_MASK] <= {broadcaster_to_ba, sent};
+                    tail <= tail + 2;
+                end
+                ka_to_fa: begin
+                    sent = !(&(pulse_value ? mem_fa | 1'b1 : mem_fa & 1'b0));
+                    mem_fa[0] <= pulse_value;
+                    queue[tail] <= {fa_to_hub, sent};
+                    tail <= tail + 1;
+                end
+                kb_to_fb: begin
+                    sent = !(&(pulse_value ? mem_fb | 1'b1 : mem_fb & 1'b0));
+                    mem_fb[0] <= pulse_value;
+                    queue[tail] <= {fb_to_hub, sent};
+                    tail <= tail + 1;
+                end
+                fa_to_hub: begin
+                    sent = !(&(pulse_value ? mem_hub | 2'b01 : mem_hub & 2'b10));
+                    mem_hub[0] <= pulse_value;
+                    queue[tail] <= {hub_to_rx, sent};
+                    tail <= tail + 1;
+                end
+                fb_to_hub: begin
+                    sent = !(&(pulse_value ? mem_hub | 2'b10 : mem_hub & 2'b01));
+                    mem_hub[1] <= pulse_value;
+                    queue[tail] <= {hub_to_rx, sent};
+                    tail <= tail + 1;
+                end
+                aa_to_ka: begin
+                    sent = !(&(pulse_value ? mem_ka | 2'b01 : mem_ka & 2'b10));
+                    mem_ka[0] <= pulse_value;
+                    queue[tail] <= {ka_to_fa, sent};
+                    tail <= tail + 1;
+                end
+                ab_to_ka: begin
+                    sent = !(&(pulse_value ? mem_ka | 2'b10 : mem_ka & 2'b01));
+                    mem_ka[1] <= pulse_value;
+                    queue[tail] <= {ka_to_fa, sent};
+                    tail <= tail + 1;
+                end
+                ba_to_kb: begin
+                    sent = !(&(pulse_value ? mem_kb | 1'b1 : mem_kb & 1'b0));
+                    mem_kb[0] <= pulse_value;
+                    queue[tail] <= {kb_to_fb, sent};
+                    tail <= tail + 1;
+                end
+                default: ;
+            endcase
+        end else if (button) begin
+            queue[tail] <= {button_to_broadcaster, 1'b0};
+            tail <= tail + 1;
+        end
+    end
+endmodule
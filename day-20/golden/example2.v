@@ -0,0 +1,88 @@
+module circuit #(
+    parameter QUEUE_BITS = 8
+) (
+    input wire clk,
+    input wire reset,
+    input wire button,
+    output wire rx,
+    output wire busy
+);
+    localparam QUEUE_MASK = (1 << QUEUE_BITS) - 1;
+
+    localparam [2:0] button_to_broadcaster = 3'd0;
+    localparam [2:0] a_to_inv = 3'd1;
+    localparam [2:0] a_to_con = 3'd2;
+    localparam [2:0] b_to_con = 3'd3;
+    localparam [2:0] broadcaster_to_a = 3'd4;
+    localparam [2:0] con_to_output = 3'd5;
+    localparam [2:0] inv_to_b = 3'd6;
+
+    reg on_a;
+    reg on_b;
+    reg [1:0] mem_con;
+    reg [0:0] mem_inv;
+    reg [3:0] queue [0:QUEUE_MASK];
+    reg [QUEUE_BITS-1:0] head;
+    reg [QUEUE_BITS-1:0] tail;
+    reg sent;
+
+    wire [2:0] pulse_wire = queue[head][3:1];
+    wire pulse_value = queue[head][0];
+    assign busy = head != tail;
+    assign rx = 1'b1;
+
+    always @(posedge clk) begin
+        if (reset) begin
+            head <= 0;
+            tail <= 0;
+            on_a <= 1'b0;
+            on_b <= 1'b0;
+            mem_con <= 0;
+            mem_inv <= 0;
+        end else if (busy) begin
+            head <= head + 1;
+            case (pulse_wire)
+                broadcaster_to_a: if (!pulse_value) begin
+                    sent = !on_a;
+                    on_a <= sent;
+                    queue[tail] <= {a_to_inv, sent};
+                    queue[(tail + 1) & QUEUE_MASK] <= {a_to_con, sent};
+                    tail <= tail + 2;
+                end
+                inv_to_b: if (!pulse_value) begin
+                    sent = !on_b;
+                    on_b <= sent;
+                    queue[tail] <= {b_to_con, sent};
+                    tail <= tail + 1;
+                end
+                button_to_broadcaster: begin
+                    sent = pulse_value;
+                    queue[tail] <= {broadcaster_to_a, sent};
+                    tail <= tail + 1;
+                end
+                a_to_con: begin
+                    sent = !(&(pulse_value ? mem_con | 2'b01 : mem_con & 2'b10));
+                    mem_con[0] <= pulse_value;
+                    queue[tail] <= {con_to_output, sent};
+                    tail <= tail + 1;
+                end
+                b_to_con: begin
+                    sent = !(&(pulse_value ? mem_con | 2'b10 : mem_con & 2'b01));
+                    mem_con[1] <= pulse_value;
+                    queue[tail] <= {con_to_output, sent};
+                    tail <= tail + 1;
+                end
+                a_to_inv: begin
+                    sent = !(&(pulse_value ? mem_inv | 1'b1 : mem_inv & 1'b0));
+                    mem_inv[0] <= pulse_value;
+                    queue[tail] <= {inv_to_b, sent};
+                    tail <= tail + 1;
+                end
+                default: ;
+            endcase
+        end else if (button) begin
+            queue[tail] <= {button_to_broadcaster, 1'b0};
+            tail <= tail + 1;
+        end
+    end
+endmodule
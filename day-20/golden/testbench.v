@@ -0,0 +1,43 @@
+`timescale 1ns / 1ps
+
+module circuit_tb;
+    parameter MAX_PRESSES = 1000000;
+
+    reg clk = 1'b0;
+    reg reset = 1'b1;
+    reg button = 1'b0;
+    wire rx;
+    wire busy;
+    integer press;
+
+    circuit dut (
+        .clk(clk),
+        .reset(reset),
+        .button(button),
+        .rx(rx),
+        .busy(busy)
+    );
+
+    always #5 clk = !clk;
+
+    // Inputs change and outputs are sampled on the falling edge, away from
+    // the rising edge the circuit works on
+    initial begin
+        @(negedge clk);
+        reset = 1'b0;
+        for (press = 1; press <= MAX_PRESSES; press = press + 1) begin
+            button = 1'b1;
+            @(negedge clk);
+            button = 1'b0;
+            while (busy) begin
+                if (!rx) begin
+                    $display("rx low on press %0d", press);
+                    $finish;
+                end
+                @(negedge clk);
+            end
+        end
+        $display("rx not low within %0d presses", MAX_PRESSES);
+        $finish;
+    end
+endmodule
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use crate::{ModuleId, ModuleType, System};
+
+// A chain of flip-flops off the broadcaster counting presses in binary, bit 0
+// first, with a conjunction that goes low once every bit wired into it is on.
+// If the conjunction also turns on every other bit and bit 0, the whole chain
+// rolls over to zero right away and the count repeats with that period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter {
+    pub hub: ModuleId,
+    pub bits: Vec<ModuleId>,
+    pub period: u64,
+    pub resets: bool,
+}
+
+// The modules that feed a sink, depth first, each with how far from the sink
+// it is. Counter hubs, flip-flops and the broadcaster are leaves, as is any
+// module already listed higher up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FanIn {
+    pub sink: ModuleId,
+    pub feeders: Vec<(usize, ModuleId)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    // Targets that were never declared, they don't send anything on
+    pub placeholders: Vec<ModuleId>,
+    pub unreachable: Vec<ModuleId>,
+    pub counters: Vec<Counter>,
+    pub fan_ins: Vec<FanIn>,
+}
+
+fn of_type(system: &System, id: ModuleId, module_type: ModuleType) -> bool {
+    system.gates[id as usize].module_type == module_type
+}
+
+fn unreachable(system: &System) -> Vec<ModuleId> {
+    let broadcaster = system.id("broadcaster").unwrap();
+    let mut reached = vec![false; system.gates.len()];
+    reached[broadcaster as usize] = true;
+    let mut queue = VecDeque::from([broadcaster]);
+    while let Some(id) = queue.pop_front() {
+        for &target in &system.gates[id as usize].targets {
+            if !reached[target as usize] {
+                reached[target as usize] = true;
+                queue.push_back(target);
+            }
+        }
+    }
+    (0..system.gates.len() as ModuleId).filter(|&id| !reached[id as usize]).collect()
+}
+
+fn counter(system: &System, first: ModuleId) -> Option<Counter> {
+    if !of_type(system, first, ModuleType::Flipflop) {
+        return None;
+    }
+
+    // Each bit carries into the only flip-flop it targets
+    let mut bits = vec![first];
+    loop {
+        let gate = &system.gates[*bits.last().unwrap() as usize];
+        let next: Vec<ModuleId> = gate
+            .targets
+            .iter()
+            .copied()
+            .filter(|&target| of_type(system, target, ModuleType::Flipflop))
+            .collect();
+        match next.as_slice() {
+            [] => break,
+            [next] if !bits.contains(next) => bits.push(*next),
+            _ => return None,
+        }
+    }
+
+    // Every bit is either wired into the hub or, to be reset, out of it
+    let mut hubs = bits.iter().flat_map(|&bit| {
+        let gate = &system.gates[bit as usize];
+        gate.targets.iter().chain(&gate.inputs).copied()
+    });
+    let hub = hubs.find(|&id| of_type(system, id, ModuleType::Conjunction))?;
+    let hub_gate = &system.gates[hub as usize];
+    let mut period = 0;
+    for (i, &bit) in bits.iter().enumerate() {
+        if hub_gate.inputs.contains(&bit) {
+            period |= 1 << i;
+        } else if !hub_gate.targets.contains(&bit) {
+            return None;
+        }
+    }
+    // Turning on the clear bits makes every bit one, and then turning off bit
+    // 0 carries all the way through
+    let resets = period & 1 == 1 && hub_gate.targets.contains(&first);
+
+    Some(Counter { hub, bits, period, resets })
+}
+
+fn fan_in(system: &System, sink: ModuleId, counters: &[Counter]) -> FanIn {
+    let mut feeders = Vec::new();
+    let mut seen = vec![sink];
+    let mut stack: Vec<(usize, ModuleId)> = system.gates[sink as usize].inputs.iter().rev().map(|&id| (1, id)).collect();
+    while let Some((depth, id)) = stack.pop() {
+        feeders.push((depth, id));
+        if seen.contains(&id) {
+            continue;
+        }
+        seen.push(id);
+        let gate = &system.gates[id as usize];
+        let leaf = gate.module_type != ModuleType::Conjunction || counters.iter().any(|counter| counter.hub == id);
+        if !leaf {
+            stack.extend(gate.inputs.iter().rev().map(|&input| (depth + 1, input)));
+        }
+    }
+    FanIn { sink, feeders }
+}
+
+pub fn analyze(system: &System) -> Analysis {
+    let placeholders: Vec<ModuleId> = (0..system.gates.len() as ModuleId)
+        .filter(|&id| of_type(system, id, ModuleType::Placeholder))
+        .collect();
+    let broadcaster = system.id("broadcaster").unwrap();
+    let counters: Vec<Counter> = system.gates[broadcaster as usize]
+        .targets
+        .iter()
+        .filter_map(|&first| counter(system, first))
+        .collect();
+    let fan_ins = placeholders.iter().map(|&sink| fan_in(system, sink, &counters)).collect();
+
+    Analysis {
+        placeholders,
+        unreachable: unreachable(system),
+        counters,
+        fan_ins,
+    }
+}
+
+impl Analysis {
+    pub fn report(&self, system: &System) -> String {
+        let names = |ids: &[ModuleId]| -> String {
+            if ids.is_empty() {
+                "none".to_string()
+            } else {
+                ids.iter().map(|&id| system.name(id)).collect::<Vec<_>>().join(", ")
+            }
+        };
+
+        let mut report = String::new();
+        writeln!(report, "Placeholder sinks: {}", names(&self.placeholders)).unwrap();
+        writeln!(report, "Unreachable from broadcaster: {}", names(&self.unreachable)).unwrap();
+
+        writeln!(report, "Counters: {}", self.counters.len()).unwrap();
+        for counter in &self.counters {
+            let bits: String = counter.bits.iter().rev().map(|&bit| system.name(bit)).collect::<Vec<_>>().join(" ");
+            let outcome = if counter.resets {
+                format!("period {}", counter.period)
+            } else {
+                format!("first fires on press {}, doesn't reset", counter.period)
+            };
+            writeln!(report, "  {}: {} bits [{}], {}", system.name(counter.hub), counter.bits.len(), bits, outcome).unwrap();
+        }
+
+        for fan_in in &self.fan_ins {
+            writeln!(report, "Fan-in to {}:", system.name(fan_in.sink)).unwrap();
+            let mut seen = Vec::new();
+            for &(depth, id) in &fan_in.feeders {
+                let gate = &system.gates[id as usize];
+                let kind = match gate.module_type {
+                    ModuleType::Broadcast => "broadcaster".to_string(),
+                    ModuleType::Flipflop => "flip-flop".to_string(),
+                    ModuleType::Button => "button".to_string(),
+                    ModuleType::Placeholder => "placeholder".to_string(),
+                    ModuleType::Conjunction => match self.counters.iter().find(|counter| counter.hub == id) {
+                        Some(counter) => format!("counter hub, period {}", counter.period),
+                        None => format!("conjunction of {}", gate.inputs.len()),
+                    },
+                };
+                let again = if seen.contains(&id) { ", see above" } else { "" };
+                seen.push(id);
+                writeln!(report, "{}{} ({}{})", "  ".repeat(depth), gate.name, kind, again).unwrap();
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A four bit counter wired for 11 = 1011 and a two bit one for 3, both
+    // feeding rx through inverters. The flip-flops off on their own can't be
+    // reached.
+    const INPUT: &str = "broadcaster -> ba, ca
+%ba -> bb, hb
+%bb -> bc, hb
+%bc -> bd
+%bd -> hb
+&hb -> ba, bc, ib
+&ib -> out
+%ca -> cb, hc
+%cb -> hc
+&hc -> ca, ic
+&ic -> out
+&out -> rx
+%lone -> loop
+%loop -> lone";
+
+    #[test]
+    fn test_analyze() {
+        let system = System::new(INPUT);
+        let analysis = analyze(&system);
+        let ids = |names: &[&str]| -> Vec<ModuleId> { names.iter().map(|name| system.id(name).unwrap()).collect() };
+
+        assert_eq!(analysis.placeholders, ids(&["rx"]));
+        assert_eq!(analysis.unreachable, ids(&["lone", "loop"]));
+        assert_eq!(
+            analysis.counters,
+            vec![
+                Counter { hub: ids(&["hb"])[0], bits: ids(&["ba", "bb", "bc", "bd"]), period: 11, resets: true },
+                Counter { hub: ids(&["hc"])[0], bits: ids(&["ca", "cb"]), period: 3, resets: true },
+            ]
+        );
+        assert_eq!(
+            analysis.report(&system),
+            "Placeholder sinks: rx
+Unreachable from broadcaster: lone, loop
+Counters: 2
+  hb: 4 bits [bd bc bb ba], period 11
+  hc: 2 bits [cb ca], period 3
+Fan-in to rx:
+  out (conjunction of 2)
+    ib (conjunction of 1)
+      hb (counter hub, period 11)
+    ic (conjunction of 1)
+      hc (counter hub, period 3)
+"
+        );
+    }
+
+    #[test]
+    fn test_periods() {
+        // Each hub goes low on the presses its period divides, and only then
+        let mut system = System::new(INPUT);
+        let analysis = analyze(&system);
+        let mut fired: Vec<Vec<usize>> = vec![Vec::new(); analysis.counters.len()];
+        for press in 1..=66 {
+            system.push_button_with(|pulse| {
+                for (i, counter) in analysis.counters.iter().enumerate() {
+                    if pulse.source == counter.hub && !pulse.value && fired[i].last() != Some(&press) {
+                        fired[i].push(press);
+                    }
+                }
+            });
+        }
+        assert_eq!(fired[0], vec![11, 22, 33, 44, 55, 66]);
+        assert_eq!(fired[1], (1..=22).map(|n| n * 3).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_not_reset() {
+        let system = System::new(
+            "broadcaster -> aa
+%aa -> ab, ka
+%ab -> ka
+&ka -> rx",
+        );
+        let analysis = analyze(&system);
+        assert_eq!(analysis.counters.len(), 1);
+        assert_eq!(analysis.counters[0].period, 3);
+        assert!(!analysis.counters[0].resets);
+    }
+}
@@ -0,0 +1,57 @@
+use aoc::Solution;
+use day_20::analysis::analyze;
+use day_20::repl::{Outcome, Session};
+use day_20::verilog::{to_verilog, TESTBENCH};
+use day_20::Day20;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+const USAGE: &str = "Usage: aoc-day20 repl|analyze|verilog|testbench [input]";
+
+fn main() {
+    // The puzzle input unless another file is given after the command
+    let args: Vec<String> = std::env::args().collect();
+    let input = match args.get(2) {
+        Some(path) => std::fs::read_to_string(path).unwrap(),
+        None => include_str!("../../input.txt").to_string(),
+    };
+    let system = match Day20::parse(&input) {
+        Ok(system) => system,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        },
+    };
+    match args.get(1).map(String::as_str) {
+        Some("repl") => repl(&input),
+        Some("analyze") => print!("{}", analyze(&system).report(&system)),
+        Some("verilog") => print!("{}", to_verilog(&system)),
+        Some("testbench") => print!("{}", TESTBENCH),
+        _ => eprintln!("{}", USAGE),
+    }
+}
+
+fn repl(input: &str) {
+    let mut session = match Session::new(input) {
+        Ok(session) => session,
+        Err(error) => return eprintln!("{}", error),
+    };
+    let mut editor = match DefaultEditor::new() {
+        Ok(editor) => editor,
+        Err(error) => return eprintln!("Can't read commands: {}", error),
+    };
+    loop {
+        let line = match editor.readline("day20> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(error) => return eprintln!("Can't read commands: {}", error),
+        };
+        // Losing a line of history is no reason to stop
+        let _ = editor.add_history_entry(line.as_str());
+        match session.execute(&line) {
+            Ok(Outcome::Continue(output)) => print!("{}", output),
+            Ok(Outcome::Quit) => break,
+            Err(error) => println!("{}", error),
+        }
+    }
+}
@@ -0,0 +1,983 @@
+pub mod analysis;
+pub mod repl;
+pub mod verilog;
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+use aoc::{Answer, ParseError, Solution};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, space0},
+    combinator::map,
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleType {
+    Broadcast,
+    Flipflop,
+    Conjunction,
+    Button,
+    Placeholder,
+}
+
+// A module as it's declared in the input, before the names are interned
+#[derive(Debug)]
+pub struct Module {
+    pub name: String,
+    pub module_type: ModuleType,
+    // Inputs aren't done in order so use a hash, the bool is the last known
+    // value, true = high, false = low.
+    pub inputs: HashMap<String, bool>,
+    // Outputs have to be sent in the right order, so use a vector
+    pub targets: Vec<String>,
+}
+impl Module {
+    pub fn new(name: String, module_type: ModuleType) -> Self {
+        Self {
+            name,
+            module_type,
+            inputs: HashMap::new(),
+            targets: Vec::new(),
+        }
+    }
+
+    pub fn add_input(&mut self, name: String) {
+        self.inputs.insert(name, false);
+    }
+
+    pub fn add_target(&mut self, name: String) {
+        self.targets.push(name);
+    }
+}
+
+// Modules are numbered in order of their names when the system is built
+pub type ModuleId = u16;
+
+// The button isn't a module of its own, it's only ever the source of the
+// pulse that starts a press
+pub const BUTTON: ModuleId = ModuleId::MAX;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pulse {
+    pub source: ModuleId,
+    pub target: ModuleId,
+    pub value: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Gate {
+    pub name: String,
+    pub module_type: ModuleType,
+    // Sorted, a conjunction remembers the last pulse from inputs[i] in bit i
+    // of its memory
+    pub inputs: Vec<ModuleId>,
+    pub targets: Vec<ModuleId>,
+    // Which bit of each target's memory this gate is
+    slots: Vec<u8>,
+    memory: u64,
+    pub output: bool,
+}
+impl Gate {
+    // What a conjunction last heard from input, always low for other modules
+    pub fn remembers(&self, input: ModuleId) -> bool {
+        match self.inputs.iter().position(|&id| id == input) {
+            Some(slot) => self.memory >> slot & 1 == 1,
+            None => panic!("{} is not an input of {}", input, self.name),
+        }
+    }
+
+    // Updates the output, returns whether a pulse is sent on to the targets
+    fn receive(&mut self, value: bool, slot: u8) -> bool {
+        match self.module_type {
+            ModuleType::Flipflop => {
+                if value {
+                    return false;
+                }
+                self.output = !self.output;
+            },
+            ModuleType::Conjunction => {
+                // Low only if all inputs are high
+                if value {
+                    self.memory |= 1 << slot;
+                } else {
+                    self.memory &= !(1 << slot);
+                }
+                self.output = self.memory.count_ones() as usize != self.inputs.len();
+            },
+            ModuleType::Button => {
+                self.output = false;
+            },
+            ModuleType::Broadcast | ModuleType::Placeholder => {
+                self.output = value;
+            },
+        }
+        true
+    }
+}
+
+// Everything that decides what a press does: which flip-flops are on and what
+// every conjunction remembers, one bit per flip-flop and a word per
+// conjunction. The outputs of other modules only ever repeat what they were
+// last sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct State {
+    flipflops: Vec<u64>,
+    memories: Vec<u64>,
+}
+
+// Pressing the button from some state leads back to it after length presses
+// once the first start presses are out of the way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub start: usize,
+    pub length: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct System {
+    pub gates: Vec<Gate>,
+    ids: HashMap<String, ModuleId>,
+    broadcaster: ModuleId,
+    // Kept between presses to save on allocations, each pulse goes with the
+    // target's memory slot for its source
+    queue: VecDeque<(Pulse, u8)>,
+    pub count_high: usize,
+    pub count_low: usize,
+}
+impl System {
+    pub fn new(input: &str) -> Self {
+        Self::from_modules(parse(input))
+    }
+
+    pub fn from_modules(modules: HashMap<String, Module>) -> Self {
+        let mut names: Vec<&String> = modules.keys().collect();
+        names.sort_unstable();
+        assert!(names.len() < BUTTON as usize, "Too many modules: {}", names.len());
+        let ids: HashMap<String, ModuleId> = names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.to_string(), id as ModuleId))
+            .collect();
+
+        let mut gates: Vec<Gate> = names
+            .iter()
+            .map(|name| {
+                let module = &modules[*name];
+                let mut inputs: Vec<ModuleId> = module.inputs.keys().filter_map(|input| ids.get(input).copied()).collect();
+                inputs.sort_unstable();
+                assert!(inputs.len() <= 64, "Too many inputs to {}: {}", name, inputs.len());
+                Gate {
+                    name: name.to_string(),
+                    module_type: module.module_type,
+                    inputs,
+                    targets: module.targets.iter().map(|target| ids[target]).collect(),
+                    slots: Vec::new(),
+                    memory: 0,
+                    output: false,
+                }
+            })
+            .collect();
+        for id in 0..gates.len() {
+            gates[id].slots = gates[id]
+                .targets
+                .iter()
+                .map(|&target| gates[target as usize].inputs.binary_search(&(id as ModuleId)).unwrap() as u8)
+                .collect();
+        }
+
+        Self {
+            gates,
+            broadcaster: ids["broadcaster"],
+            ids,
+            queue: VecDeque::new(),
+            count_high: 0,
+            count_low: 0,
+        }
+    }
+
+    pub fn id(&self, name: &str) -> Option<ModuleId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: ModuleId) -> &str {
+        if id == BUTTON {
+            "button"
+        } else {
+            &self.gates[id as usize].name
+        }
+    }
+
+    pub fn gate(&self, name: &str) -> &Gate {
+        &self.gates[self.ids[name] as usize]
+    }
+
+    pub fn push_button(&mut self) {
+        self.push_button_with(|_| {});
+    }
+
+    // Same as push_button, calling observe with every pulse as it's delivered
+    pub fn push_button_with(&mut self, observe: impl FnMut(&Pulse)) {
+        self.press(observe, |_, _| {});
+    }
+
+    // Same as push_button, recording the state after every round of pulses
+    // in the trace
+    pub fn push_button_traced(&mut self, trace: &mut Trace) {
+        trace.begin_press();
+        self.press(|_| {}, |system, round| trace.record(system, round));
+    }
+
+    // For every input of target, the first press on which it sends target a
+    // high pulse, pressing the button until they all have
+    pub fn first_high_presses(&mut self, target: ModuleId) -> Vec<usize> {
+        let inputs = self.gates[target as usize].inputs.clone();
+        let mut presses = vec![0; inputs.len()];
+        let mut found = 0;
+        let mut press = 0;
+        while found < inputs.len() {
+            press += 1;
+            self.push_button_with(|pulse| {
+                if pulse.value && pulse.target == target {
+                    let slot = inputs.binary_search(&pulse.source).unwrap();
+                    if presses[slot] == 0 {
+                        presses[slot] = press;
+                        found += 1;
+                    }
+                }
+            });
+        }
+        presses
+    }
+
+    pub fn state(&self) -> State {
+        let mut flipflops = vec![0; self.gates.len().div_ceil(64)];
+        let mut memories = Vec::new();
+        for (id, gate) in self.gates.iter().enumerate() {
+            match gate.module_type {
+                ModuleType::Flipflop => flipflops[id / 64] |= (gate.output as u64) << (id % 64),
+                ModuleType::Conjunction => memories.push(gate.memory),
+                _ => (),
+            }
+        }
+        State { flipflops, memories }
+    }
+
+    // Finds the period of the states the system goes through from the
+    // current one with Brent's algorithm, giving up after limit presses
+    pub fn find_period(&self, limit: usize) -> Option<Period> {
+        // Look for a repeat in windows of doubling size
+        let mut tortoise = self.state();
+        let mut hare = self.clone();
+        hare.push_button();
+        let mut presses = 1;
+        let mut power = 1;
+        let mut length = 1;
+        let mut state = hare.state();
+        while state != tortoise {
+            if presses >= limit {
+                return None;
+            }
+            if power == length {
+                tortoise = state;
+                power *= 2;
+                length = 0;
+            }
+            hare.push_button();
+            presses += 1;
+            length += 1;
+            state = hare.state();
+        }
+
+        // With the hare a whole period ahead, they meet where the loop starts
+        let mut tortoise = self.clone();
+        let mut hare = self.clone();
+        for _ in 0..length {
+            hare.push_button();
+        }
+        let mut start = 0;
+        while tortoise.state() != hare.state() {
+            tortoise.push_button();
+            hare.push_button();
+            start += 1;
+        }
+
+        Some(Period { start, length })
+    }
+
+    // The number of high and low pulses sent by that many presses from the
+    // current state, going round the period as many times as needed instead
+    // of simulating them all. None if there's no period within limit presses.
+    pub fn pulses_after(&self, presses: u64, limit: usize) -> Option<(u64, u64)> {
+        let period = self.find_period(limit)?;
+
+        // The pulses sent by the first i presses, for as many as it takes to
+        // go round once
+        let mut system = self.clone();
+        system.count_high = 0;
+        system.count_low = 0;
+        let mut counts = vec![(0, 0)];
+        for _ in 0..period.start + period.length {
+            system.push_button();
+            counts.push((system.count_high as u64, system.count_low as u64));
+        }
+
+        let start = period.start as u64;
+        let length = period.length as u64;
+        if presses <= start + length {
+            return Some(counts[presses as usize]);
+        }
+        let rounds = (presses - start) / length;
+        let rest = ((presses - start) % length) as usize;
+        let (start_high, start_low) = counts[period.start];
+        let (loop_high, loop_low) = counts[period.start + period.length];
+        let (rest_high, rest_low) = counts[period.start + rest];
+        Some((
+            rest_high + rounds * (loop_high - start_high),
+            rest_low + rounds * (loop_low - start_low),
+        ))
+    }
+
+    // Pulses are delivered in rounds: the button's pulse is round 1, the
+    // pulses sent in response to it round 2, and so on
+    fn press(&mut self, mut observe: impl FnMut(&Pulse), mut after_round: impl FnMut(&System, usize)) {
+        let pulse = Pulse {
+            source: BUTTON,
+            target: self.broadcaster,
+            value: false,
+        };
+        self.queue.push_back((pulse, 0));
+
+        let mut round = 0;
+        while !self.queue.is_empty() {
+            round += 1;
+            for _ in 0..self.queue.len() {
+                let (pulse, slot) = self.queue.pop_front().unwrap();
+                if pulse.value {
+                    self.count_high += 1;
+                } else {
+                    self.count_low += 1;
+                }
+
+                observe(&pulse);
+                let gate = &mut self.gates[pulse.target as usize];
+                if gate.receive(pulse.value, slot) {
+                    for (&target, &slot) in gate.targets.iter().zip(&gate.slots) {
+                        let sent = Pulse {
+                            source: pulse.target,
+                            target,
+                            value: gate.output,
+                        };
+                        self.queue.push_back((sent, slot));
+                    }
+                }
+            }
+            after_round(self, round);
+        }
+    }
+
+    // The wiring as a Graphviz graph. Every module type has its own shape,
+    // placeholders for undeclared targets are dashed, and each edge is
+    // numbered by its position in the source's list of targets, which is the
+    // order pulses are sent in. With annotate, nodes also show their current
+    // output, with high outputs filled in.
+    pub fn to_dot(&self, annotate: bool) -> String {
+        let mut dot = String::from("digraph modules {\n");
+        for gate in &self.gates {
+            let mut attributes = vec![match gate.module_type {
+                ModuleType::Broadcast => "shape=house",
+                ModuleType::Flipflop => "shape=box",
+                ModuleType::Conjunction => "shape=invtriangle",
+                ModuleType::Button => "shape=invhouse",
+                ModuleType::Placeholder => "shape=ellipse, style=dashed",
+            }
+            .to_string()];
+            if annotate {
+                let output = if gate.output { "high" } else { "low" };
+                attributes.push(format!("label=\"{}\\n{}\"", gate.name, output));
+                if gate.output {
+                    attributes.push("style=filled, fillcolor=gold".to_string());
+                }
+            }
+            writeln!(dot, "    \"{}\" [{}];", gate.name, attributes.join(", ")).unwrap();
+        }
+        for gate in &self.gates {
+            for (i, &target) in gate.targets.iter().enumerate() {
+                writeln!(dot, "    \"{}\" -> \"{}\" [label=\"{}\"];", gate.name, self.name(target), i + 1).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// A signal in a Trace, which is either a module's output or what a
+// conjunction remembers about one of its inputs
+#[derive(Debug, Clone)]
+enum Signal {
+    Output { module: ModuleId },
+    Memory { module: ModuleId, slot: u8 },
+}
+impl Signal {
+    fn value(&self, system: &System) -> bool {
+        match *self {
+            Signal::Output { module } => system.gates[module as usize].output,
+            Signal::Memory { module, slot } => system.gates[module as usize].memory >> slot & 1 == 1,
+        }
+    }
+}
+
+// VCD identifiers are short strings of printable ASCII, from ! to ~
+fn vcd_id(mut index: usize) -> String {
+    let mut id = String::new();
+    loop {
+        id.push((b'!' + (index % 94) as u8) as char);
+        index /= 94;
+        if index == 0 {
+            return id;
+        }
+        index -= 1;
+    }
+}
+
+// A waveform of the system as a Value Change Dump. Each round of pulses is one
+// unit of time, and the press and round numbers are signals of their own so
+// that a viewer can show where in the simulation a change happened. Every
+// module's output is a signal in the outputs scope, and every conjunction
+// has a scope under memory with a signal for each of its inputs.
+#[derive(Debug, Clone)]
+pub struct Trace {
+    signals: Vec<Signal>,
+    values: Vec<bool>,
+    vcd: String,
+    time: u64,
+    press: usize,
+}
+impl Trace {
+    const PRESS_ID: &'static str = "!";
+    const ROUND_ID: &'static str = "\"";
+
+    // Starts the trace from the current state of the system
+    pub fn new(system: &System) -> Self {
+        let mut vcd = String::new();
+        writeln!(vcd, "$timescale 1ns $end").unwrap();
+        writeln!(vcd, "$scope module system $end").unwrap();
+        writeln!(vcd, "$var integer 32 {} press $end", Self::PRESS_ID).unwrap();
+        writeln!(vcd, "$var integer 32 {} round $end", Self::ROUND_ID).unwrap();
+
+        // The first two identifiers are taken by press and round, and the
+        // modules are already in order of their names
+        let mut signals = Vec::new();
+        writeln!(vcd, "$scope module outputs $end").unwrap();
+        for (id, gate) in system.gates.iter().enumerate() {
+            writeln!(vcd, "$var wire 1 {} {} $end", vcd_id(signals.len() + 2), gate.name).unwrap();
+            signals.push(Signal::Output { module: id as ModuleId });
+        }
+        writeln!(vcd, "$upscope $end").unwrap();
+
+        writeln!(vcd, "$scope module memory $end").unwrap();
+        for (id, gate) in system.gates.iter().enumerate() {
+            if gate.module_type != ModuleType::Conjunction {
+                continue;
+            }
+            writeln!(vcd, "$scope module {} $end", gate.name).unwrap();
+            for (slot, &input) in gate.inputs.iter().enumerate() {
+                writeln!(vcd, "$var wire 1 {} {} $end", vcd_id(signals.len() + 2), system.name(input)).unwrap();
+                signals.push(Signal::Memory { module: id as ModuleId, slot: slot as u8 });
+            }
+            writeln!(vcd, "$upscope $end").unwrap();
+        }
+        writeln!(vcd, "$upscope $end").unwrap();
+        writeln!(vcd, "$upscope $end").unwrap();
+        writeln!(vcd, "$enddefinitions $end").unwrap();
+
+        let values: Vec<bool> = signals.iter().map(|signal| signal.value(system)).collect();
+        writeln!(vcd, "#0").unwrap();
+        writeln!(vcd, "$dumpvars").unwrap();
+        writeln!(vcd, "b0 {}", Self::PRESS_ID).unwrap();
+        writeln!(vcd, "b0 {}", Self::ROUND_ID).unwrap();
+        for (i, value) in values.iter().enumerate() {
+            writeln!(vcd, "{}{}", *value as u8, vcd_id(i + 2)).unwrap();
+        }
+        writeln!(vcd, "$end").unwrap();
+
+        Self { signals, values, vcd, time: 0, press: 0 }
+    }
+
+    fn begin_press(&mut self) {
+        self.press += 1;
+    }
+
+    // Dumps whatever changed since the last round
+    fn record(&mut self, system: &System, round: usize) {
+        self.time += 1;
+        writeln!(self.vcd, "#{}", self.time).unwrap();
+        if round == 1 {
+            writeln!(self.vcd, "b{:b} {}", self.press, Self::PRESS_ID).unwrap();
+        }
+        writeln!(self.vcd, "b{:b} {}", round, Self::ROUND_ID).unwrap();
+        for (i, signal) in self.signals.iter().enumerate() {
+            let value = signal.value(system);
+            if value != self.values[i] {
+                self.values[i] = value;
+                writeln!(self.vcd, "{}{}", value as u8, vcd_id(i + 2)).unwrap();
+            }
+        }
+    }
+
+    pub fn vcd(&self) -> &str {
+        &self.vcd
+    }
+}
+
+pub fn parse_lines(input: &str) -> HashMap<String, Module> {
+    let mut modules = HashMap::new();
+
+    for line in input.lines() {
+        let (_, module) = parse_line(line).unwrap();
+        modules.insert(module.name.clone(), module);
+    }
+
+    modules
+}
+
+pub fn set_target_inputs(modules: &mut HashMap<String, Module>, input: String, targets: &[String]) {
+    for target in targets.iter() {
+        if !modules.contains_key(target) {
+            let mut t = Module::new(target.clone(), ModuleType::Placeholder);
+            t.add_input(input.clone());
+            modules.insert(target.clone(), t);
+            continue;
+        }
+
+        let t = modules.get_mut(target).unwrap();
+        t.add_input(input.clone());
+    }
+}
+
+pub fn parse(input: &str) -> HashMap<String, Module> {
+    let mut modules = parse_lines(input);
+
+    let mut inputs: Vec<(String, Vec<String>)> = Vec::new();
+    for (_, module) in modules.iter_mut() {
+        let name = module.name.clone();
+        let targets = module.targets.clone();
+
+        inputs.push((name, targets));
+    }
+
+    for (name, targets) in inputs.iter() {
+        set_target_inputs(&mut modules, name.clone(), targets);
+    }
+    set_target_inputs(&mut modules, "button".to_string(), &["broadcaster".to_string()]);
+
+    modules
+}
+
+pub fn parse_line(input: &str) -> IResult<&str, Module> {
+    let (input, module_type) = alt((
+        map(tag("%"), |_| ModuleType::Flipflop),
+        map(tag("&"), |_| ModuleType::Conjunction),
+        map(space0, |_| ModuleType::Broadcast),
+    ))(input)?;
+
+    let (input, (name, _, targets)) = tuple((
+        alpha1,
+        tag(" -> "),
+        separated_list1(tag(", "), alpha1),
+    ))(input)?;
+
+    let mut module = Module::new(name.to_string(), module_type);
+    for target in targets.iter() {
+        module.add_target(target.to_string());
+    }
+
+    Ok((input, module))
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub struct Day20;
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Parsed = System;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        // parse_lines panics on lines it can't read, so check them first
+        for (i, line) in input.lines().enumerate() {
+            if !matches!(parse_line(line), Ok(("", _))) {
+                return Err(ParseError::Syntax { line: i + 1, message: format!("Expected a module: {}", line) });
+            }
+        }
+        if !input.lines().any(|line| line.starts_with("broadcaster ")) {
+            return Err(ParseError::Invalid("No broadcaster".to_string()));
+        }
+        Ok(System::new(input))
+    }
+
+    fn part1(system: &Self::Parsed) -> Answer {
+        let mut system = system.clone();
+
+        for _ in 0..1000 {
+            system.push_button();
+        }
+
+        (system.count_high * system.count_low).into()
+    }
+
+    fn part2(system: &Self::Parsed) -> Answer {
+        let mut system = system.clone();
+
+        // rx is fed by a single conjunction, which only sends it a low pulse
+        // once all of its inputs have sent it a high pulse. Each of those
+        // inputs goes high on a fixed cycle, so rx gets its low pulse at the
+        // least common multiple of the cycles.
+        let Some(rx) = system.id("rx") else {
+            return Answer::Unsolved;
+        };
+        let Some(feeder) = system.gates.iter().position(|gate| gate.targets.contains(&rx)) else {
+            return Answer::Unsolved;
+        };
+
+        system
+            .first_high_presses(feeder as ModuleId)
+            .into_iter()
+            .fold(1, |lcm, cycle| lcm / gcd(lcm, cycle) * cycle)
+            .into()
+    }
+}
+
+aoc::register!(Day20);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const INPUT: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    #[test]
+    fn test_parse_line() {
+        let result = parse_line("broadcast -> a, b, c").unwrap();
+        let module = result.1;
+        assert_eq!(module.name, "broadcast".to_string());
+        assert_eq!(module.module_type, ModuleType::Broadcast);
+        assert_eq!(module.targets, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+
+        let result = parse_line("%a -> b").unwrap();
+        let module = result.1;
+        assert_eq!(module.name, "a".to_string());
+        assert_eq!(module.module_type, ModuleType::Flipflop);
+        assert_eq!(module.targets, vec!["b".to_string()]);
+
+        let result = parse_line("&inv -> a").unwrap();
+        let module = result.1;
+        assert_eq!(module.name, "inv".to_string());
+        assert_eq!(module.module_type, ModuleType::Conjunction);
+        assert_eq!(module.targets, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let result = parse(INPUT1);
+        assert_eq!(result.len(), 5);
+        assert_eq!(result["broadcaster"].name, "broadcaster".to_string());
+        assert_eq!(result["broadcaster"].module_type, ModuleType::Broadcast);
+        assert_eq!(result["broadcaster"].targets, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(result["broadcaster"].inputs.len(), 1);
+        assert_eq!(result["a"].name, "a".to_string());
+        assert_eq!(result["a"].module_type, ModuleType::Flipflop);
+        assert_eq!(result["a"].targets, vec!["b".to_string()]);
+        assert_eq!(result["b"].name, "b".to_string());
+        assert_eq!(result["b"].module_type, ModuleType::Flipflop);
+        assert_eq!(result["b"].targets, vec!["c".to_string()]);
+        assert_eq!(result["c"].name, "c".to_string());
+        assert_eq!(result["c"].module_type, ModuleType::Flipflop);
+        assert_eq!(result["c"].targets, vec!["inv".to_string()]);
+        assert_eq!(result["inv"].name, "inv".to_string());
+        assert_eq!(result["inv"].module_type, ModuleType::Conjunction);
+        assert_eq!(result["inv"].targets, vec!["a".to_string()]);
+    }
+
+    /*
+    #[test]
+    fn test_push_button() {
+        let mut modules = parse(INPUT);
+        push_button(&mut modules);
+        assert_eq!(modules["broadcaster"].output, false);
+        assert_eq!(modules["a"].output, false);
+        assert_eq!(modules["b"].output, false);
+        assert_eq!(modules["inv"].output, true);
+        assert_eq!(modules["output"].output, false);
+    }
+    */
+
+    #[test]
+    fn test_system_parse() {
+        let system = System::new(INPUT);
+        println!("system: {:?}", system);
+        println!("system.gates: {:?}", system.gates);
+        println!("system.gate(\"output\"): {:?}", system.gate("output"));
+    }
+
+    #[test]
+    fn test_push_button1() {
+        let mut system = System::new(INPUT1);
+        system.push_button();
+        assert!(!system.gate("broadcaster").output);
+        assert!(!system.gate("a").output);
+        assert!(!system.gate("b").output);
+        assert!(!system.gate("c").output);
+        assert!(system.gate("inv").output);
+        assert_eq!(system.count_high, 4);
+        assert_eq!(system.count_low, 8);
+    }
+
+    #[test]
+    fn test_push_button() {
+        let mut system = System::new(INPUT);
+
+        println!(" ===================== PUSH 1 ===================== ");
+        system.push_button();
+        assert!(!system.gate("output").output);
+        assert_eq!(system.count_high, 4);
+        assert_eq!(system.count_low, 4);
+        
+        println!(" ===================== PUSH 2 ===================== ");
+        system.push_button();
+        assert!(system.gate("output").output);
+        assert_eq!(system.count_high, 6);
+        assert_eq!(system.count_low, 8);
+
+        println!(" ===================== PUSH 3 ===================== ");
+        system.push_button();
+        assert!(system.gate("output").output);
+        assert_eq!(system.count_high, 9);
+        assert_eq!(system.count_low, 13);
+
+        println!(" ===================== PUSH 4 ===================== ");
+        system.push_button();
+        assert!(system.gate("output").output);
+        assert_eq!(system.count_high, 11);
+        assert_eq!(system.count_low, 17);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let system = System::new(INPUT1);
+        assert_eq!(
+            system.to_dot(false),
+            r#"digraph modules {
+    "a" [shape=box];
+    "b" [shape=box];
+    "broadcaster" [shape=house];
+    "c" [shape=box];
+    "inv" [shape=invtriangle];
+    "a" -> "b" [label="1"];
+    "b" -> "c" [label="1"];
+    "broadcaster" -> "a" [label="1"];
+    "broadcaster" -> "b" [label="2"];
+    "broadcaster" -> "c" [label="3"];
+    "c" -> "inv" [label="1"];
+    "inv" -> "a" [label="1"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_to_dot_annotated() {
+        let mut system = System::new(INPUT);
+        system.push_button();
+        assert_eq!(
+            system.to_dot(true),
+            r#"digraph modules {
+    "a" [shape=box, label="a\nhigh", style=filled, fillcolor=gold];
+    "b" [shape=box, label="b\nhigh", style=filled, fillcolor=gold];
+    "broadcaster" [shape=house, label="broadcaster\nlow"];
+    "con" [shape=invtriangle, label="con\nlow"];
+    "inv" [shape=invtriangle, label="inv\nlow"];
+    "output" [shape=ellipse, style=dashed, label="output\nlow"];
+    "a" -> "inv" [label="1"];
+    "a" -> "con" [label="2"];
+    "b" -> "con" [label="1"];
+    "broadcaster" -> "a" [label="1"];
+    "con" -> "output" [label="1"];
+    "inv" -> "b" [label="1"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_vcd_id() {
+        assert_eq!(vcd_id(0), "!");
+        assert_eq!(vcd_id(93), "~");
+        assert_eq!(vcd_id(94), "!!");
+        assert_eq!(vcd_id(95), "\"!");
+        assert_eq!(vcd_id(94 + 94 * 94), "!!!");
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut system = System::new(INPUT);
+        let mut trace = Trace::new(&system);
+        system.push_button_traced(&mut trace);
+        assert_eq!(
+            trace.vcd(),
+            "$timescale 1ns $end
+$scope module system $end
+$var integer 32 ! press $end
+$var integer 32 \" round $end
+$scope module outputs $end
+$var wire 1 # a $end
+$var wire 1 $ b $end
+$var wire 1 % broadcaster $end
+$var wire 1 & con $end
+$var wire 1 ' inv $end
+$var wire 1 ( output $end
+$upscope $end
+$scope module memory $end
+$scope module con $end
+$var wire 1 ) a $end
+$var wire 1 * b $end
+$upscope $end
+$scope module inv $end
+$var wire 1 + a $end
+$upscope $end
+$upscope $end
+$upscope $end
+$enddefinitions $end
+#0
+$dumpvars
+b0 !
+b0 \"
+0#
+0$
+0%
+0&
+0'
+0(
+0)
+0*
+0+
+$end
+#1
+b1 !
+b1 \"
+#2
+b10 \"
+1#
+#3
+b11 \"
+1&
+1)
+1+
+#4
+b100 \"
+1$
+1(
+#5
+b101 \"
+0&
+1*
+#6
+b110 \"
+0(
+"
+        );
+    }
+
+    // Periods the slow way, remembering every state that's been seen
+    fn period_by_hashing(input: &str) -> Period {
+        let mut system = System::new(input);
+        let mut seen: HashMap<State, usize> = HashMap::new();
+        let mut presses = 0;
+        loop {
+            if let Some(start) = seen.insert(system.state(), presses) {
+                return Period { start, length: presses - start };
+            }
+            system.push_button();
+            presses += 1;
+        }
+    }
+
+    #[test]
+    fn test_find_period() {
+        let counters = "broadcaster -> aa, ba
+%aa -> ab, ka
+%ab -> ka
+&ka -> fa
+&fa -> hub
+%ba -> kb
+&kb -> fb
+&fb -> hub
+&hub -> rx";
+        for input in [INPUT1, INPUT, counters] {
+            let system = System::new(input);
+            assert_eq!(system.find_period(1000), Some(period_by_hashing(input)));
+        }
+        assert_eq!(System::new(INPUT1).find_period(1000), Some(Period { start: 0, length: 1 }));
+        assert_eq!(System::new(INPUT).find_period(1000), Some(Period { start: 0, length: 4 }));
+        assert_eq!(System::new(INPUT).find_period(3), None);
+
+        // The second press leaves ka remembering that aa is high
+        let mut system = System::new(counters);
+        system.push_button();
+        system.push_button();
+        assert_ne!(system.state(), System::new(counters).state());
+        assert_eq!(system.find_period(1000), Some(Period { start: 0, length: 4 }));
+    }
+
+    #[test]
+    fn test_pulses_after() {
+        assert_eq!(System::new(INPUT1).pulses_after(1000, 1000), Some((4000, 8000)));
+        assert_eq!(System::new(INPUT).pulses_after(1000, 1000), Some((2750, 4250)));
+        for presses in 0..20 {
+            let mut system = System::new(INPUT);
+            for _ in 0..presses {
+                system.push_button();
+            }
+            let expected = (system.count_high as u64, system.count_low as u64);
+            assert_eq!(System::new(INPUT).pulses_after(presses, 1000), Some(expected));
+        }
+        assert_eq!(
+            System::new(INPUT).pulses_after(1_000_000_000_000, 1000),
+            Some((2_750_000_000_000, 4_250_000_000_000))
+        );
+    }
+
+    #[test]
+    fn test_solution_parse() {
+        let system = Day20::parse(INPUT1).unwrap();
+        assert_eq!(Day20::part1(&system), Answer::UInt(32000000));
+        // There's no rx to wait for in the examples
+        assert_eq!(Day20::part2(&system), Answer::Unsolved);
+
+        assert_eq!(
+            Day20::parse("broadcaster -> a\n%a -> \n").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Expected a module: %a -> ".to_string() }
+        );
+        assert_eq!(Day20::parse("%a -> b").unwrap_err(), ParseError::Invalid("No broadcaster".to_string()));
+    }
+}
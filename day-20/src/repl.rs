@@ -0,0 +1,277 @@
+use std::fmt;
+use std::fmt::Write;
+
+use aoc::{ParseError, Solution};
+
+use crate::analysis::analyze;
+use crate::{Day20, ModuleId, ModuleType, Pulse, System};
+
+pub const HELP: &str = "press [n]                  press the button n times, once by default
+state [module]             pulse counts, or a module's output and memory
+watch <module>             show the pulses a module sends while pressing
+unwatch <module>           stop showing them
+break when <module> high   stop pressing after a press where the module sends high
+break clear                remove all breakpoints
+trace on|off               show every pulse while pressing
+analyze                    report on how the circuit is wired
+reset                      go back to before the first press
+help                       this text
+quit                       leave
+";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommandError {
+    Unknown(String),
+    UnknownModule(String),
+    Usage(&'static str),
+}
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::Unknown(command) => write!(f, "Unknown command: {}, try help", command),
+            CommandError::UnknownModule(name) => write!(f, "Unknown module: {}", name),
+            CommandError::Usage(usage) => write!(f, "Usage: {}", usage),
+        }
+    }
+}
+impl std::error::Error for CommandError {}
+
+// What a command asks of whoever reads the lines
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Continue(String),
+    Quit,
+}
+
+fn format_pulse(system: &System, pulse: &Pulse) -> String {
+    let value = if pulse.value { "high" } else { "low" };
+    format!("{} -{}-> {}", system.name(pulse.source), value, system.name(pulse.target))
+}
+
+// A circuit being stepped through by hand, one command line at a time
+pub struct Session {
+    // The circuit before the first press, for reset
+    start: System,
+    system: System,
+    presses: usize,
+    watches: Vec<ModuleId>,
+    breakpoints: Vec<ModuleId>,
+    trace: bool,
+}
+impl Session {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let system = Day20::parse(input)?;
+        Ok(Self {
+            start: system.clone(),
+            system,
+            presses: 0,
+            watches: Vec::new(),
+            breakpoints: Vec::new(),
+            trace: false,
+        })
+    }
+
+    pub fn system(&self) -> &System {
+        &self.system
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<Outcome, CommandError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let output = match words.as_slice() {
+            [] => String::new(),
+            ["press"] => self.press(1),
+            ["press", n] => match n.parse() {
+                Ok(n) => self.press(n),
+                Err(_) => return Err(CommandError::Usage("press [n]")),
+            },
+            ["state"] => format!(
+                "{} presses, {} high and {} low pulses\n",
+                self.presses, self.system.count_high, self.system.count_low
+            ),
+            ["state", name] => self.state(self.module(name)?),
+            ["watch", name] => {
+                let id = self.module(name)?;
+                if !self.watches.contains(&id) {
+                    self.watches.push(id);
+                }
+                String::new()
+            },
+            ["unwatch", name] => {
+                let id = self.module(name)?;
+                self.watches.retain(|&watch| watch != id);
+                String::new()
+            },
+            ["break", "when", name, "high"] => {
+                let id = self.module(name)?;
+                if !self.breakpoints.contains(&id) {
+                    self.breakpoints.push(id);
+                }
+                String::new()
+            },
+            ["break", "clear"] => {
+                self.breakpoints.clear();
+                String::new()
+            },
+            ["break", ..] => return Err(CommandError::Usage("break when <module> high | break clear")),
+            ["trace", "on"] => {
+                self.trace = true;
+                String::new()
+            },
+            ["trace", "off"] => {
+                self.trace = false;
+                String::new()
+            },
+            ["reset"] => {
+                self.system = self.start.clone();
+                self.presses = 0;
+                String::new()
+            },
+            ["analyze"] => analyze(&self.system).report(&self.system),
+            ["help"] => HELP.to_string(),
+            ["quit"] | ["exit"] => return Ok(Outcome::Quit),
+            ["press", ..] => return Err(CommandError::Usage("press [n]")),
+            ["state", ..] => return Err(CommandError::Usage("state [module]")),
+            ["watch", ..] => return Err(CommandError::Usage("watch <module>")),
+            ["unwatch", ..] => return Err(CommandError::Usage("unwatch <module>")),
+            ["trace", ..] => return Err(CommandError::Usage("trace on|off")),
+            [command, ..] => return Err(CommandError::Unknown(command.to_string())),
+        };
+        Ok(Outcome::Continue(output))
+    }
+
+    fn module(&self, name: &str) -> Result<ModuleId, CommandError> {
+        self.system.id(name).ok_or_else(|| CommandError::UnknownModule(name.to_string()))
+    }
+
+    // Presses up to n times, stopping early after a press that trips a
+    // breakpoint
+    fn press(&mut self, n: usize) -> String {
+        let mut output = String::new();
+        for _ in 0..n {
+            self.presses += 1;
+            let presses = self.presses;
+            let mut fired: Vec<ModuleId> = Vec::new();
+            let Session { system, watches, breakpoints, trace, .. } = self;
+            let mut pulses: Vec<Pulse> = Vec::new();
+            system.push_button_with(|pulse| {
+                if *trace || watches.contains(&pulse.source) {
+                    pulses.push(*pulse);
+                }
+                if pulse.value && breakpoints.contains(&pulse.source) && !fired.contains(&pulse.source) {
+                    fired.push(pulse.source);
+                }
+            });
+            for pulse in &pulses {
+                writeln!(output, "press {}: {}", presses, format_pulse(system, pulse)).unwrap();
+            }
+            if !fired.is_empty() {
+                let names: Vec<&str> = fired.iter().map(|&id| system.name(id)).collect();
+                writeln!(output, "break on press {}: {} sent high", presses, names.join(", ")).unwrap();
+                break;
+            }
+        }
+        output
+    }
+
+    fn state(&self, id: ModuleId) -> String {
+        let gate = &self.system.gates[id as usize];
+        let kind = match gate.module_type {
+            ModuleType::Broadcast => "broadcaster",
+            ModuleType::Flipflop => "flip-flop",
+            ModuleType::Conjunction => "conjunction",
+            ModuleType::Button => "button",
+            ModuleType::Placeholder => "placeholder",
+        };
+        let level = |value: bool| if value { "high" } else { "low" };
+        let mut output = format!("{} ({}): {}\n", gate.name, kind, level(gate.output));
+        if gate.module_type == ModuleType::Conjunction {
+            for &input in &gate.inputs {
+                writeln!(output, "  {}: {}", self.system.name(input), level(gate.remembers(input))).unwrap();
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    fn run(session: &mut Session, line: &str) -> String {
+        match session.execute(line).unwrap() {
+            Outcome::Continue(output) => output,
+            Outcome::Quit => panic!("Quit on {}", line),
+        }
+    }
+
+    #[test]
+    fn test_press_and_state() {
+        let mut session = Session::new(INPUT).unwrap();
+        assert_eq!(run(&mut session, "press 2"), "");
+        assert_eq!(run(&mut session, "state"), "2 presses, 6 high and 8 low pulses\n");
+        assert_eq!(run(&mut session, "state con"), "con (conjunction): high\n  a: low\n  b: high\n");
+        assert_eq!(run(&mut session, "state b"), "b (flip-flop): high\n");
+        assert_eq!(run(&mut session, "reset"), "");
+        assert_eq!(run(&mut session, "state"), "0 presses, 0 high and 0 low pulses\n");
+        assert_eq!(run(&mut session, "state b"), "b (flip-flop): low\n");
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert_eq!(
+            Session::new("broadcaster -> a\n%a ->").err(),
+            Some(ParseError::Syntax { line: 2, message: "Expected a module: %a ->".to_string() })
+        );
+        assert_eq!(Session::new("%a -> b").err(), Some(ParseError::Invalid("No broadcaster".to_string())));
+    }
+
+    #[test]
+    fn test_trace_and_watch() {
+        let mut session = Session::new(INPUT).unwrap();
+        run(&mut session, "trace on");
+        assert_eq!(
+            run(&mut session, "press"),
+            "press 1: button -low-> broadcaster
+press 1: broadcaster -low-> a
+press 1: a -high-> inv
+press 1: a -high-> con
+press 1: inv -low-> b
+press 1: con -high-> output
+press 1: b -high-> con
+press 1: con -low-> output
+"
+        );
+        run(&mut session, "trace off");
+        run(&mut session, "watch inv");
+        assert_eq!(run(&mut session, "press"), "press 2: inv -high-> b\n");
+        run(&mut session, "unwatch inv");
+        assert_eq!(run(&mut session, "press"), "");
+    }
+
+    #[test]
+    fn test_break() {
+        let mut session = Session::new(INPUT).unwrap();
+        run(&mut session, "break when inv high");
+        assert_eq!(run(&mut session, "press 10"), "break on press 2: inv sent high\n");
+        assert_eq!(run(&mut session, "press 10"), "break on press 4: inv sent high\n");
+        run(&mut session, "break clear");
+        assert_eq!(run(&mut session, "press 10"), "");
+        assert_eq!(run(&mut session, "state"), "14 presses, 39 high and 59 low pulses\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut session = Session::new(INPUT).unwrap();
+        assert_eq!(session.execute("jump"), Err(CommandError::Unknown("jump".to_string())));
+        assert_eq!(session.execute("state rx"), Err(CommandError::UnknownModule("rx".to_string())));
+        assert_eq!(session.execute("press lots"), Err(CommandError::Usage("press [n]")));
+        assert_eq!(session.execute("trace maybe"), Err(CommandError::Usage("trace on|off")));
+        assert_eq!(session.execute("quit"), Ok(Outcome::Quit));
+    }
+}
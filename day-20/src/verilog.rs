@@ -0,0 +1,352 @@
+use std::fmt::Write;
+
+use crate::{ModuleId, ModuleType, System, BUTTON};
+
+// Drives a circuit from to_verilog: presses the button, waits for the pulses
+// to die down and reports the first press that sends rx a low pulse
+pub const TESTBENCH: &str = "`timescale 1ns / 1ps
+
+module circuit_tb;
+    parameter MAX_PRESSES = 1000000;
+
+    reg clk = 1'b0;
+    reg reset = 1'b1;
+    reg button = 1'b0;
+    wire rx;
+    wire busy;
+    integer press;
+
+    circuit dut (
+        .clk(clk),
+        .reset(reset),
+        .button(button),
+        .rx(rx),
+        .busy(busy)
+    );
+
+    always #5 clk = !clk;
+
+    // Inputs change and outputs are sampled on the falling edge, away from
+    // the rising edge the circuit works on
+    initial begin
+        @(negedge clk);
+        reset = 1'b0;
+        for (press = 1; press <= MAX_PRESSES; press = press + 1) begin
+            button = 1'b1;
+            @(negedge clk);
+            button = 1'b0;
+            while (busy) begin
+                if (!rx) begin
+                    $display(\"rx low on press %0d\", press);
+                    $finish;
+                end
+                @(negedge clk);
+            end
+        end
+        $display(\"rx not low within %0d presses\", MAX_PRESSES);
+        $finish;
+    end
+endmodule
+";
+
+// The system as a Verilog module. Pulses wait in a queue just like they do
+// in System::press, and every clock cycle delivers the one at the head, so
+// modules see them in the same order. Every wire between two modules has a
+// number, and the queue holds the wire and the value of each pulse. Flip-flops
+// keep whether they're on in on_<name>, conjunctions what they remember of
+// inputs[i] in bit i of mem_<name>. The button is only listened to while the
+// queue is empty, busy says whether it is, and rx goes low for the cycle a low
+// pulse is delivered to rx.
+pub fn to_verilog(system: &System) -> String {
+    let wire = |source: ModuleId, target: ModuleId| format!("{}_to_{}", system.name(source), system.name(target));
+    let broadcaster = system.id("broadcaster").unwrap();
+
+    // A module that lists a target twice sends it two pulses, but over the
+    // same wire
+    let mut wires = vec![(BUTTON, broadcaster)];
+    for (id, gate) in system.gates.iter().enumerate() {
+        for (i, &target) in gate.targets.iter().enumerate() {
+            if !gate.targets[..i].contains(&target) {
+                wires.push((id as ModuleId, target));
+            }
+        }
+    }
+    let wire_bits = (usize::BITS - (wires.len() - 1).leading_zeros()).max(1) as usize;
+
+    let mut verilog = String::new();
+    writeln!(verilog, "module circuit #(").unwrap();
+    writeln!(verilog, "    parameter QUEUE_BITS = 8").unwrap();
+    writeln!(verilog, ") (").unwrap();
+    writeln!(verilog, "    input wire clk,").unwrap();
+    writeln!(verilog, "    input wire reset,").unwrap();
+    writeln!(verilog, "    input wire button,").unwrap();
+    writeln!(verilog, "    output wire rx,").unwrap();
+    writeln!(verilog, "    output wire busy").unwrap();
+    writeln!(verilog, ");").unwrap();
+    writeln!(verilog, "    localparam QUEUE_MASK = (1 << QUEUE_BITS) - 1;").unwrap();
+    writeln!(verilog).unwrap();
+    for (i, &(source, target)) in wires.iter().enumerate() {
+        writeln!(verilog, "    localparam [{}:0] {} = {}'d{};", wire_bits - 1, wire(source, target), wire_bits, i).unwrap();
+    }
+
+    writeln!(verilog).unwrap();
+    for gate in &system.gates {
+        match gate.module_type {
+            ModuleType::Flipflop => writeln!(verilog, "    reg on_{};", gate.name).unwrap(),
+            ModuleType::Conjunction if !gate.inputs.is_empty() => {
+                writeln!(verilog, "    reg [{}:0] mem_{};", gate.inputs.len() - 1, gate.name).unwrap()
+            },
+            _ => (),
+        }
+    }
+    writeln!(verilog, "    reg [{}:0] queue [0:QUEUE_MASK];", wire_bits).unwrap();
+    writeln!(verilog, "    reg [QUEUE_BITS-1:0] head;").unwrap();
+    writeln!(verilog, "    reg [QUEUE_BITS-1:0] tail;").unwrap();
+    writeln!(verilog, "    reg sent;").unwrap();
+    writeln!(verilog).unwrap();
+    writeln!(verilog, "    wire [{}:0] pulse_wire = queue[head][{}:1];", wire_bits - 1, wire_bits).unwrap();
+    writeln!(verilog, "    wire pulse_value = queue[head][0];").unwrap();
+    writeln!(verilog, "    assign busy = head != tail;").unwrap();
+    match system.id("rx") {
+        Some(rx) => {
+            let into_rx: Vec<String> = wires
+                .iter()
+                .filter(|&&(_, target)| target == rx)
+                .map(|&(source, target)| format!("pulse_wire == {}", wire(source, target)))
+                .collect();
+            writeln!(verilog, "    assign rx = !(busy && !pulse_value && ({}));", into_rx.join(" || ")).unwrap();
+        },
+        None => writeln!(verilog, "    assign rx = 1'b1;").unwrap(),
+    }
+
+    writeln!(verilog).unwrap();
+    writeln!(verilog, "    always @(posedge clk) begin").unwrap();
+    writeln!(verilog, "        if (reset) begin").unwrap();
+    writeln!(verilog, "            head <= 0;").unwrap();
+    writeln!(verilog, "            tail <= 0;").unwrap();
+    for gate in &system.gates {
+        match gate.module_type {
+            ModuleType::Flipflop => writeln!(verilog, "            on_{} <= 1'b0;", gate.name).unwrap(),
+            ModuleType::Conjunction if !gate.inputs.is_empty() => {
+                writeln!(verilog, "            mem_{} <= 0;", gate.name).unwrap()
+            },
+            _ => (),
+        }
+    }
+    writeln!(verilog, "        end else if (busy) begin").unwrap();
+    writeln!(verilog, "            head <= head + 1;").unwrap();
+    writeln!(verilog, "            case (pulse_wire)").unwrap();
+
+    // Queues a pulse with the value in sent on every wire out of a module
+    let send = |verilog: &mut String, id: ModuleId| {
+        let targets = &system.gates[id as usize].targets;
+        for (i, &target) in targets.iter().enumerate() {
+            let slot = if i == 0 { "tail".to_string() } else { format!("(tail + {}) & QUEUE_MASK", i) };
+            writeln!(verilog, "                    queue[{}] <= {{{}, sent}};", slot, wire(id, target)).unwrap();
+        }
+        if !targets.is_empty() {
+            writeln!(verilog, "                    tail <= tail + {};", targets.len()).unwrap();
+        }
+    };
+    for (id, gate) in system.gates.iter().enumerate() {
+        let id = id as ModuleId;
+        let into: Vec<String> = wires
+            .iter()
+            .filter(|&&(_, target)| target == id)
+            .map(|&(source, target)| wire(source, target))
+            .collect();
+        if into.is_empty() {
+            continue;
+        }
+        match gate.module_type {
+            ModuleType::Broadcast => {
+                writeln!(verilog, "                {}: begin", into.join(", ")).unwrap();
+                writeln!(verilog, "                    sent = pulse_value;").unwrap();
+                send(&mut verilog, id);
+                writeln!(verilog, "                end").unwrap();
+            },
+            ModuleType::Flipflop => {
+                writeln!(verilog, "                {}: if (!pulse_value) begin", into.join(", ")).unwrap();
+                writeln!(verilog, "                    sent = !on_{};", gate.name).unwrap();
+                writeln!(verilog, "                    on_{} <= sent;", gate.name).unwrap();
+                send(&mut verilog, id);
+                writeln!(verilog, "                end").unwrap();
+            },
+            ModuleType::Conjunction => {
+                // Low only if all inputs are high, counting the one just sent
+                let width = gate.inputs.len();
+                for (slot, &input) in gate.inputs.iter().enumerate() {
+                    let bit = 1 << slot;
+                    let all = (1 << width) - 1;
+                    writeln!(verilog, "                {}: begin", wire(input, id)).unwrap();
+                    writeln!(
+                        verilog,
+                        "                    sent = !(&(pulse_value ? mem_{0} | {1}'b{2:03$b} : mem_{0} & {1}'b{4:03$b}));",
+                        gate.name, width, bit, width, all ^ bit
+                    )
+                    .unwrap();
+                    writeln!(verilog, "                    mem_{}[{}] <= pulse_value;", gate.name, slot).unwrap();
+                    send(&mut verilog, id);
+                    writeln!(verilog, "                end").unwrap();
+                }
+            },
+            // Nothing happens at a placeholder, though rx shows what it's sent
+            ModuleType::Button | ModuleType::Placeholder => (),
+        }
+    }
+    writeln!(verilog, "                default: ;").unwrap();
+    writeln!(verilog, "            endcase").unwrap();
+    writeln!(verilog, "        end else if (button) begin").unwrap();
+    writeln!(verilog, "            queue[tail] <= {{{}, 1'b0}};", wire(BUTTON, broadcaster)).unwrap();
+    writeln!(verilog, "            tail <= tail + 1;").unwrap();
+    writeln!(verilog, "        end").unwrap();
+    writeln!(verilog, "    end").unwrap();
+    writeln!(verilog, "endmodule").unwrap();
+    verilog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const INPUT2: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    const COUNTERS: &str = "broadcaster -> aa, ba
+%aa -> ab, ka
+%ab -> ka
+&ka -> fa
+&fa -> hub
+%ba -> kb
+&kb -> fb
+&fb -> hub
+&hub -> rx";
+
+    const DUPLICATES: &str = "broadcaster -> a, a, b
+%a -> con, con
+%b -> con
+&con -> rx";
+
+    // Presses the button a thousand times and counts the pulses delivered
+    const COUNTING_TESTBENCH: &str = "module counting_tb;
+    reg clk = 1'b0;
+    reg reset = 1'b1;
+    reg button = 1'b0;
+    wire rx;
+    wire busy;
+    integer press;
+    integer high = 0;
+    integer low = 0;
+
+    circuit dut (
+        .clk(clk),
+        .reset(reset),
+        .button(button),
+        .rx(rx),
+        .busy(busy)
+    );
+
+    always #5 clk = !clk;
+
+    initial begin
+        @(negedge clk);
+        reset = 1'b0;
+        for (press = 0; press < 1000; press = press + 1) begin
+            button = 1'b1;
+            @(negedge clk);
+            button = 1'b0;
+            while (busy) begin
+                if (dut.pulse_value) high = high + 1;
+                else low = low + 1;
+                @(negedge clk);
+            end
+        end
+        $display(\"high %0d low %0d\", high, low);
+        $finish;
+    end
+endmodule
+";
+
+    #[test]
+    fn test_golden() {
+        assert_eq!(to_verilog(&System::new(INPUT1)), include_str!("../golden/example1.v"));
+        assert_eq!(to_verilog(&System::new(INPUT2)), include_str!("../golden/example2.v"));
+        assert_eq!(to_verilog(&System::new(COUNTERS)), include_str!("../golden/counters.v"));
+        assert_eq!(TESTBENCH, include_str!("../golden/testbench.v"));
+    }
+
+    #[test]
+    fn test_structure() {
+        let system = System::new(COUNTERS);
+        let verilog = to_verilog(&system);
+        assert!(verilog.starts_with("module circuit #(\n"));
+        assert!(verilog.ends_with("endmodule\n"));
+
+        // A wire for the button and every target, and a case for every wire
+        // but the one into rx, as no flip-flop has two inputs here
+        let wires = 1 + system.gates.iter().map(|gate| gate.targets.len()).sum::<usize>();
+        assert_eq!(verilog.matches("    localparam [3:0] ").count(), wires);
+        assert_eq!(verilog.matches(": begin\n").count() + verilog.matches(": if (!pulse_value) begin\n").count(), wires - 1);
+        assert!(verilog.contains("    localparam [3:0] button_to_broadcaster = 4'd0;\n"));
+        assert!(verilog.contains("    assign rx = !(busy && !pulse_value && (pulse_wire == hub_to_rx));\n"));
+        assert!(verilog.contains("    reg [1:0] mem_hub;\n"));
+        assert!(verilog.contains("    reg on_aa;\n"));
+        assert!(to_verilog(&System::new(INPUT2)).contains("    assign rx = 1'b1;\n"));
+    }
+
+    #[test]
+    fn test_duplicate_targets() {
+        let verilog = to_verilog(&System::new(DUPLICATES));
+        assert_eq!(verilog.matches(" broadcaster_to_a = ").count(), 1);
+        assert_eq!(verilog.matches(" a_to_con = ").count(), 1);
+        assert!(verilog.contains("                broadcaster_to_a: if (!pulse_value) begin\n"));
+        // Still a pulse for every time a target is listed
+        assert!(verilog.contains("                    queue[(tail + 1) & QUEUE_MASK] <= {a_to_con, sent};\n"));
+        assert!(verilog.contains("                    tail <= tail + 2;\n"));
+    }
+
+    // Runs the circuits in Icarus Verilog, where it's installed, and checks
+    // they deliver as many high and low pulses as System does
+    #[test]
+    fn test_simulated() {
+        use std::process::Command;
+
+        if Command::new("iverilog").arg("-V").output().is_err() {
+            eprintln!("iverilog isn't installed, not simulating");
+            return;
+        }
+        let dir = std::env::temp_dir().join(format!("day-20-verilog-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("testbench.v"), COUNTING_TESTBENCH).unwrap();
+        for input in [INPUT1, INPUT2, COUNTERS, DUPLICATES] {
+            let mut system = System::new(input);
+            std::fs::write(dir.join("circuit.v"), to_verilog(&system)).unwrap();
+            for _ in 0..1000 {
+                system.push_button();
+            }
+
+            let compiled = Command::new("iverilog")
+                .arg("-o")
+                .arg(dir.join("circuit.vvp"))
+                .arg(dir.join("circuit.v"))
+                .arg(dir.join("testbench.v"))
+                .status()
+                .unwrap();
+            assert!(compiled.success(), "iverilog failed on {:?}", input);
+            let output = Command::new("vvp").arg(dir.join("circuit.vvp")).output().unwrap();
+            let output = String::from_utf8(output.stdout).unwrap();
+            let expected = format!("high {} low {}\n", system.count_high, system.count_low);
+            assert!(output.contains(&expected), "Expected {:?} from {:?}, got {:?}", expected, input, output);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
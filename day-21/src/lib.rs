@@ -0,0 +1,214 @@
+use aoc::{Answer, ParseError, Solution};
+
+pub const STEPS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn neighbours(&self, max_width: usize, max_height: usize) -> Vec<Pos> {
+        let mut result = Vec::new();
+        if self.x > 0 {
+            result.push(Pos::new(self.x - 1, self.y));
+        }
+        if self.y > 0 {
+            result.push(Pos::new(self.x, self.y - 1));
+        }
+        if self.x < max_width - 1 {
+            result.push(Pos::new(self.x + 1, self.y));
+        }
+        if self.y < max_height - 1 {
+            result.push(Pos::new(self.x, self.y + 1));
+        }
+        result
+    }
+}
+
+// A grid of visitable positions, a grid of currently occupied positions and
+// the starting position
+pub type Garden = (Vec<Vec<bool>>, Vec<Vec<bool>>, Pos);
+
+pub fn parse(input: &str) -> Result<Garden, ParseError> {
+    let mut plots = Vec::new();
+    let mut occupied = Vec::new();
+    let mut start = None;
+    for (y, line) in input.lines().enumerate() {
+        let syntax = |message: String| ParseError::Syntax { line: y + 1, message };
+        let mut row_plots = Vec::new();
+        let mut row_occupied = Vec::new();
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                '.' => {
+                    row_plots.push(true);
+                    row_occupied.push(false);
+                },
+                '#' => {
+                    row_plots.push(false);
+                    row_occupied.push(false);
+                },
+                'S' => {
+                    row_plots.push(true);
+                    row_occupied.push(true);
+                    if start.replace(Pos::new(x, y)).is_some() {
+                        return Err(syntax("More than one start".to_string()));
+                    }
+                },
+                _ => return Err(syntax(format!("Unexpected char: {}", c))),
+            }
+        }
+        if plots.first().is_some_and(|first: &Vec<bool>| first.len() != row_plots.len()) {
+            return Err(syntax("Rows differ in length".to_string()));
+        }
+        plots.push(row_plots);
+        occupied.push(row_occupied);
+    }
+    let start = start.ok_or_else(|| ParseError::Invalid("No start".to_string()))?;
+    Ok((plots, occupied, start))
+}
+
+// Returns a new grid of occupied positions after one step. Currently occupied
+// positions are no longer occupied. All orthogonal neighbours of currently
+// occupied positions that are open plots are now occupied.
+pub fn step(plots: &[Vec<bool>], occupied: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let mut result = Vec::new();
+
+    for (y, row) in occupied.iter().enumerate() {
+        let mut new_row = Vec::new();
+        for (x, is_occupied) in row.iter().enumerate() {
+            if !plots[y][x] || *is_occupied {
+                new_row.push(false);
+                continue;
+            }
+
+            let neighbours = Pos::new(x, y).neighbours(plots[0].len(), plots.len());
+            new_row.push(neighbours.iter().any(|neighbour| occupied[neighbour.y][neighbour.x]));
+        }
+        result.push(new_row);
+    }
+
+    result
+}
+
+pub fn count_occupied(occupied: &[Vec<bool>]) -> usize {
+    occupied.iter().map(|row| row.iter().filter(|is_occupied| **is_occupied).count()).sum()
+}
+
+pub struct Day21;
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Parsed = Garden;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part1((plots, occupied, _start): &Self::Parsed) -> Answer {
+        let mut occupied = occupied.clone();
+        for _ in 0..STEPS {
+            occupied = step(plots, &occupied);
+        }
+
+        count_occupied(&occupied).into()
+    }
+
+    fn part2(_: &Self::Parsed) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+aoc::register!(Day21);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    fn steps(n: usize) -> usize {
+        let (plots, mut occupied, _) = parse(INPUT).unwrap();
+        for _ in 0..n {
+            occupied = step(&plots, &occupied);
+        }
+        count_occupied(&occupied)
+    }
+
+    #[test]
+    fn test_parse() {
+        let (plots, occupied, start) = parse(INPUT).unwrap();
+        assert_eq!(plots.len(), 11);
+        assert_eq!(plots[0].len(), 11);
+        assert_eq!(plots[10].len(), 11);
+        assert_eq!(occupied.len(), 11);
+        assert_eq!(occupied[0].len(), 11);
+        assert_eq!(occupied[10].len(), 11);
+
+        assert_eq!(start, Pos::new(5, 5));
+
+        assert!(plots[start.y][start.x]);
+        assert!(occupied[start.y][start.x]);
+        assert_eq!(count_occupied(&occupied), 1);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse("S.\n.x").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "Unexpected char: x".to_string() }
+        );
+        assert_eq!(
+            parse("S.\nS.").unwrap_err(),
+            ParseError::Syntax { line: 2, message: "More than one start".to_string() }
+        );
+        assert_eq!(parse("..\n..").unwrap_err(), ParseError::Invalid("No start".to_string()));
+        assert!(parse("S.\n.").is_err());
+    }
+
+    #[test]
+    fn test_step() {
+        let (plots, occupied, start) = parse(INPUT).unwrap();
+        let occupied = step(&plots, &occupied);
+        assert_eq!(occupied.len(), 11);
+        assert_eq!(occupied[0].len(), 11);
+        assert_eq!(occupied[10].len(), 11);
+
+        assert!(!occupied[start.y][start.x]);
+        assert_eq!(occupied[start.y - 1][start.x], plots[start.y - 1][start.x]);
+        assert_eq!(occupied[start.y + 1][start.x], plots[start.y + 1][start.x]);
+        assert_eq!(occupied[start.y][start.x - 1], plots[start.y][start.x - 1]);
+        assert_eq!(occupied[start.y][start.x + 1], plots[start.y][start.x + 1]);
+
+        assert_eq!(count_occupied(&occupied), 2);
+    }
+
+    #[test]
+    fn test_two_steps() {
+        assert_eq!(steps(2), 4);
+    }
+
+    #[test]
+    fn test_three_steps() {
+        assert_eq!(steps(3), 6);
+    }
+
+    #[test]
+    fn test_six_steps() {
+        assert_eq!(steps(6), 16);
+    }
+}
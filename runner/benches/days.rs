@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+use aoc::{Answer, Day, Solution};
+use criterion::{black_box, criterion_group, Criterion};
+
+// Parts slower than this are sampled less, so that the whole suite doesn't
+// take forever
+const SLOW: Duration = Duration::from_millis(10);
+const SAMPLES: usize = 100;
+const SLOW_SAMPLES: usize = 10;
+
+fn bench<S: Solution>(c: &mut Criterion, day: &Day) {
+    let input = day.input().unwrap();
+    let parsed = S::parse(&input).unwrap();
+
+    // The sample size sticks for the rest of the group, so every benchmark
+    // sets its own
+    let mut group = c.benchmark_group(format!("day-{:02}", day.number));
+    group.sample_size(SAMPLES);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    for (name, part) in [("part1", S::part1 as fn(&S::Parsed) -> Answer), ("part2", S::part2)] {
+        let start = Instant::now();
+        if part(&parsed) == Answer::Unsolved {
+            continue;
+        }
+        group.sample_size(if start.elapsed() > SLOW { SLOW_SAMPLES } else { SAMPLES });
+        group.bench_function(name, |b| b.iter(|| part(black_box(&parsed))));
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench::<day_01::Day01>(c, &day_01::DAY);
+    bench::<day_02::Day02>(c, &day_02::DAY);
+    bench::<day_03::Day03>(c, &day_03::DAY);
+    bench::<day_04::Day04>(c, &day_04::DAY);
+    bench::<day_05::Day05>(c, &day_05::DAY);
+    bench::<day_07::Day07>(c, &day_07::DAY);
+    bench::<day_08::Day08>(c, &day_08::DAY);
+    bench::<day_09::Day09>(c, &day_09::DAY);
+    bench::<day_10::Day10>(c, &day_10::DAY);
+    bench::<day_11::Day11>(c, &day_11::DAY);
+    bench::<day_12::Day12>(c, &day_12::DAY);
+    bench::<day_13::Day13>(c, &day_13::DAY);
+    bench::<day_14::Day14>(c, &day_14::DAY);
+    bench::<day_15::Day15>(c, &day_15::DAY);
+    bench::<day_16::Day16>(c, &day_16::DAY);
+    bench::<day_19::Day19>(c, &day_19::DAY);
+    bench::<day_20::Day20>(c, &day_20::DAY);
+    bench::<day_21::Day21>(c, &day_21::DAY);
+}
+
+// Where criterion keeps its results, found the same way it does, minus
+// asking cargo metadata
+fn criterion_home() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        PathBuf::from(home)
+    } else if let Some(target) = std::env::var_os("CARGO_TARGET_DIR") {
+        PathBuf::from(target).join("criterion")
+    } else {
+        PathBuf::from("target/criterion")
+    }
+}
+
+// The mean time of a benchmark, if it was measured since `since`. Results
+// from earlier runs are left on disk for benchmarks that were filtered out
+// or skipped, and they don't count.
+fn mean(group: &str, function: &str, since: SystemTime) -> Option<Duration> {
+    let path = criterion_home().join(group).join(function).join("new/estimates.json");
+    if std::fs::metadata(&path).and_then(|metadata| metadata.modified()).ok()? < since {
+        return None;
+    }
+    let estimates: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    let nanos = estimates["mean"]["point_estimate"].as_f64()?;
+    Some(Duration::from_secs_f64(nanos / 1e9))
+}
+
+fn millis(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => format!("{:.3} ms", duration.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+// Where the year's runtime goes, from the parts measured since `since`
+fn summary(since: SystemTime) {
+    let rows: Vec<(String, [Option<Duration>; 3])> = runner::DAYS
+        .iter()
+        .map(|day| {
+            let group = format!("day-{:02}", day.number);
+            let times = ["parse", "part1", "part2"].map(|function| mean(&group, function, since));
+            (group, times)
+        })
+        .filter(|(_, times)| times.iter().any(Option::is_some))
+        .collect();
+    let day_total = |times: &[Option<Duration>; 3]| times.iter().flatten().sum::<Duration>();
+    if rows.is_empty() {
+        return;
+    }
+    let total: Duration = rows.iter().map(|(_, times)| day_total(times)).sum();
+
+    println!();
+    println!("{:<6}  {:>12}  {:>12}  {:>12}  {:>12}  {:>6}", "Day", "Parse", "Part 1", "Part 2", "Total", "Share");
+    for (group, times) in rows.iter() {
+        println!(
+            "{:<6}  {:>12}  {:>12}  {:>12}  {:>12}  {:>5.1}%",
+            group,
+            millis(times[0]),
+            millis(times[1]),
+            millis(times[2]),
+            millis(Some(day_total(times))),
+            100.0 * day_total(times).as_secs_f64() / total.as_secs_f64()
+        );
+    }
+    println!("{:<6}  {:>12}  {:>12}  {:>12}  {:>12}", "Year", "", "", "", millis(Some(total)));
+}
+
+criterion_group!(benches, days);
+
+fn main() {
+    let start = SystemTime::now();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    summary(start);
+}
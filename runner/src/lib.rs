@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use aoc::{Day, ParseError, Run};
+
+pub const DAYS: [Day; 18] = [
+    day_01::DAY,
+    day_02::DAY,
+    day_03::DAY,
+    day_04::DAY,
+    day_05::DAY,
+    day_07::DAY,
+    day_08::DAY,
+    day_09::DAY,
+    day_10::DAY,
+    day_11::DAY,
+    day_12::DAY,
+    day_13::DAY,
+    day_14::DAY,
+    day_15::DAY,
+    day_16::DAY,
+    day_19::DAY,
+    day_20::DAY,
+    day_21::DAY,
+];
+
+// The steps of a day that are timed, in the order their times are kept in
+pub const STEPS: [&str; 3] = ["parse", "part1", "part2"];
+
+pub type Times = [Duration; 3];
+
+// Steps faster than this vary too much from run to run to say whether they
+// got slower
+const NOISE: Duration = Duration::from_micros(100);
+
+// Each day's times, as recorded in a baseline file
+pub type Baseline = BTreeMap<u8, Times>;
+
+// Why a day gave no answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Parse(ParseError),
+    Panic(String),
+}
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Parse(err) => write!(f, "Parse error: {}", err),
+            Failure::Panic(message) => write!(f, "Panicked: {}", message),
+        }
+    }
+}
+
+// Runs a day, turning a panic into a Failure so that the other days still get
+// to run
+pub fn run(day: &Day, input: &str) -> Result<Run, Failure> {
+    match std::panic::catch_unwind(|| (day.run)(input)) {
+        Ok(result) => result.map_err(Failure::Parse),
+        Err(payload) => {
+            let message = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+                (Some(message), _) => message.to_string(),
+                (_, Some(message)) => message.clone(),
+                _ => "unknown".to_string(),
+            };
+            Err(Failure::Panic(message))
+        },
+    }
+}
+
+// Runs a day on its input a number of times and keeps the fastest time of
+// each step, which is the one least disturbed by whatever else is running
+pub fn measure(day: &Day, input: &str, runs: usize) -> Result<(Run, Times), Failure> {
+    let run = run(day, input)?;
+    let mut times = [run.parse, run.part1.1, run.part2.1];
+    for _ in 1..runs {
+        let again = self::run(day, input)?;
+        for (time, new) in times.iter_mut().zip([again.parse, again.part1.1, again.part2.1]) {
+            *time = (*time).min(new);
+        }
+    }
+    Ok((run, times))
+}
+
+// The baseline as JSON, with times in milliseconds:
+// {"day-01": {"parse": 0.149, "part1": 0.099, "part2": 0.111}, ...}
+pub fn baseline_to_json(baseline: &Baseline) -> String {
+    let days: serde_json::Map<String, serde_json::Value> = baseline
+        .iter()
+        .map(|(day, times)| {
+            let steps = STEPS.iter().zip(times).map(|(step, time)| (step.to_string(), (time.as_micros() as f64 / 1000.0).into()));
+            (format!("day-{:02}", day), serde_json::Value::Object(steps.collect()))
+        })
+        .collect();
+    serde_json::to_string_pretty(&days).unwrap()
+}
+
+pub fn baseline_from_json(json: &str) -> Result<Baseline, String> {
+    let days: serde_json::Map<String, serde_json::Value> = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    let mut baseline = Baseline::new();
+    for (name, steps) in days.iter() {
+        let day = name.strip_prefix("day-").and_then(|day| day.parse().ok()).ok_or(format!("Bad day: {}", name))?;
+        let mut times = [Duration::ZERO; 3];
+        for (time, step) in times.iter_mut().zip(STEPS) {
+            let millis = steps[step].as_f64().ok_or(format!("No {} time for {}", step, name))?;
+            *time = Duration::from_secs_f64(millis / 1000.0);
+        }
+        baseline.insert(day, times);
+    }
+    Ok(baseline)
+}
+
+// What happened to each day in a run: its times, or why it gave no answers
+pub type Results = BTreeMap<u8, Result<Times, Failure>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    // More than the allowed percentage slower than the baseline time
+    Slower { step: &'static str, time: Duration, baseline: Duration, percent: f64 },
+    OverBudget { step: &'static str, time: Duration, budget: Duration },
+    Failed(Failure),
+    // In the baseline but not run at all
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub problem: Problem,
+}
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let millis = |time: Duration| time.as_secs_f64() * 1000.0;
+        write!(f, "Day {:02}", self.day)?;
+        match &self.problem {
+            Problem::Slower { step, time, baseline, percent } => write!(
+                f,
+                " {}: {:.3} ms, {:.0}% slower than the baseline {:.3} ms",
+                step,
+                millis(*time),
+                percent,
+                millis(*baseline)
+            ),
+            Problem::OverBudget { step, time, budget } => {
+                write!(f, " {}: {:.3} ms, over the budget of {} ms", step, millis(*time), budget.as_millis())
+            },
+            Problem::Failed(failure) => write!(f, ": {}", failure),
+            Problem::Missing => write!(f, ": in the baseline but not run"),
+        }
+    }
+}
+
+// How long each step may take: the default, unless the step has a budget of
+// its own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Budget {
+    pub default: Duration,
+    pub steps: BTreeMap<(u8, &'static str), Duration>,
+}
+impl Budget {
+    pub fn new(default: Duration) -> Self {
+        Self { default, steps: BTreeMap::new() }
+    }
+
+    pub fn get(&self, day: u8, step: &str) -> Duration {
+        self.steps.get(&(day, step)).copied().unwrap_or(self.default)
+    }
+
+    // Sets the budget of one step from day.step=ms, as in 14.part2=60
+    pub fn set(&mut self, spec: &str) -> Result<(), String> {
+        let bad = || format!("Expected day.step=ms, like 14.part2=60: {}", spec);
+        let (step, millis) = spec.split_once('=').ok_or_else(bad)?;
+        let (day, step) = step.split_once('.').ok_or_else(bad)?;
+        let day = day.parse().map_err(|_| bad())?;
+        let step = STEPS.into_iter().find(|name| *name == step).ok_or_else(bad)?;
+        let millis = millis.parse().map_err(|_| bad())?;
+        self.steps.insert((day, step), Duration::from_millis(millis));
+        Ok(())
+    }
+}
+
+// Every step that got more than slower percent slower than in the baseline,
+// or that takes longer than its budget, every day that failed and every day
+// in the baseline that wasn't run. Days missing from the baseline are only
+// held to their budgets.
+pub fn compare(baseline: &Baseline, current: &Results, slower: f64, budgets: &Budget) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+    for (&day, result) in current.iter() {
+        let times = match result {
+            Ok(times) => times,
+            Err(failure) => {
+                regressions.push(Regression { day, problem: Problem::Failed(failure.clone()) });
+                continue;
+            },
+        };
+        for (i, &time) in times.iter().enumerate() {
+            let step = STEPS[i];
+            let mut regression = |problem| regressions.push(Regression { day, problem });
+            if let Some(before) = baseline.get(&day).map(|times| times[i]) {
+                let percent = 100.0 * (time.as_secs_f64() / before.as_secs_f64() - 1.0);
+                if percent > slower && time > NOISE {
+                    regression(Problem::Slower { step, time, baseline: before, percent });
+                }
+            }
+            let budget = budgets.get(day, step);
+            if time > budget {
+                regression(Problem::OverBudget { step, time, budget });
+            }
+        }
+    }
+    for &day in baseline.keys().filter(|day| !current.contains_key(day)) {
+        regressions.push(Regression { day, problem: Problem::Missing });
+    }
+    regressions.sort_by_key(|regression| regression.day);
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Panics;
+    impl aoc::Solution for Panics {
+        const DAY: u8 = 0;
+        type Parsed = ();
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            match input {
+                "" => Err(ParseError::Invalid("Empty".to_string())),
+                _ => Ok(()),
+            }
+        }
+
+        fn part1(_: &Self::Parsed) -> aoc::Answer {
+            aoc::Answer::UInt(1)
+        }
+
+        fn part2(_: &Self::Parsed) -> aoc::Answer {
+            panic!("No rx")
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let day = Day::new::<Panics>("");
+        assert_eq!(run(&day, "x").unwrap_err(), Failure::Panic("No rx".to_string()));
+        assert_eq!(run(&day, "").unwrap_err(), Failure::Parse(ParseError::Invalid("Empty".to_string())));
+        assert!(measure(&DAYS[0], &DAYS[0].input().unwrap(), 2).is_ok());
+    }
+
+    #[test]
+    fn test_days() {
+        assert!(DAYS.windows(2).all(|pair| pair[0].number < pair[1].number));
+        assert!(DAYS.iter().all(|day| day.dir.ends_with(&format!("day-{:02}", day.number))));
+    }
+
+    fn millis(millis: &[u64; 3]) -> Times {
+        millis.map(Duration::from_millis)
+    }
+
+    #[test]
+    fn test_baseline_json() {
+        let baseline = Baseline::from([(1, millis(&[1, 2, 3])), (21, millis(&[4, 50, 0]))]);
+        let json = baseline_to_json(&baseline);
+        assert!(json.contains("\"day-21\""));
+        assert_eq!(baseline_from_json(&json).unwrap(), baseline);
+
+        assert!(baseline_from_json(r#"{"21": {"parse": 1, "part1": 2, "part2": 3}}"#).is_err());
+        assert!(baseline_from_json(r#"{"day-21": {"parse": 1, "part1": 2}}"#).is_err());
+    }
+
+    // What a run that gave every day its times would have
+    fn results(times: &Baseline) -> Results {
+        times.iter().map(|(&day, &times)| (day, Ok(times))).collect()
+    }
+
+    fn step(regression: &Regression) -> Option<&'static str> {
+        match regression.problem {
+            Problem::Slower { step, .. } | Problem::OverBudget { step, .. } => Some(step),
+            Problem::Failed(_) | Problem::Missing => None,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = Baseline::from([(1, millis(&[10, 10, 10]))]);
+        let current = results(&Baseline::from([(1, millis(&[11, 13, 150])), (2, millis(&[1, 200, 1]))]));
+        let budget = Duration::from_millis(100);
+        let regressions = compare(&baseline, &current, 20.0, &Budget::new(budget));
+
+        let found: Vec<(u8, Option<&str>)> =
+            regressions.iter().map(|regression| (regression.day, step(regression))).collect();
+        assert_eq!(found, vec![(1, Some("part1")), (1, Some("part2")), (1, Some("part2")), (2, Some("part1"))]);
+        assert!(matches!(regressions[0].problem, Problem::Slower { percent, .. } if (percent - 30.0).abs() < 1e-6));
+        assert_eq!(
+            regressions[2].problem,
+            Problem::OverBudget { step: "part2", time: Duration::from_millis(150), budget }
+        );
+        assert_eq!(regressions[3].to_string(), "Day 02 part1: 200.000 ms, over the budget of 100 ms");
+
+        assert!(compare(&baseline, &results(&baseline), 20.0, &Budget::new(budget)).is_empty());
+
+        let fast = Baseline::from([(1, [Duration::from_micros(10); 3])]);
+        let twice = Baseline::from([(1, [Duration::from_micros(20); 3])]);
+        assert!(compare(&fast, &results(&twice), 20.0, &Budget::new(budget)).is_empty());
+    }
+
+    #[test]
+    fn test_compare_failed_and_missing() {
+        let baseline = Baseline::from([(1, millis(&[1, 1, 1])), (2, millis(&[1, 1, 1])), (3, millis(&[1, 1, 1]))]);
+        let mut current = results(&Baseline::from([(3, millis(&[1, 1, 1]))]));
+        current.insert(1, Err(Failure::Panic("No rx".to_string())));
+        current.insert(4, Err(Failure::Parse(ParseError::Invalid("Empty".to_string()))));
+        let regressions = compare(&baseline, &current, 20.0, &Budget::new(Duration::from_millis(100)));
+
+        let found: Vec<String> = regressions.iter().map(Regression::to_string).collect();
+        assert_eq!(
+            found,
+            vec!["Day 01: Panicked: No rx", "Day 02: in the baseline but not run", "Day 04: Parse error: Empty"]
+        );
+        assert_eq!(regressions[1].problem, Problem::Missing);
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut budget = Budget::new(Duration::from_millis(100));
+        budget.set("2.part1=250").unwrap();
+        budget.set("1.parse=5").unwrap();
+        assert_eq!(budget.get(2, "part1"), Duration::from_millis(250));
+        assert_eq!(budget.get(2, "part2"), Duration::from_millis(100));
+
+        let current = results(&Baseline::from([(1, millis(&[10, 1, 1])), (2, millis(&[1, 200, 1]))]));
+        let regressions = compare(&Baseline::new(), &current, 20.0, &budget);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].to_string(), "Day 01 parse: 10.000 ms, over the budget of 5 ms");
+
+        assert!(budget.set("2.part3=10").is_err());
+        assert!(budget.set("2.part1").is_err());
+        assert!(budget.set("x.part1=10").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use runner::{baseline_from_json, baseline_to_json, compare, measure, Baseline, Budget, Results, DAYS};
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+// Takes the values of an option like --runs 5 out of the arguments, however
+// many times it's given
+fn options(args: &mut Vec<String>, name: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    while let Some(position) = args.iter().position(|arg| arg == name) {
+        args.remove(position);
+        assert!(position < args.len(), "{} needs a value", name);
+        values.push(args.remove(position));
+    }
+    values
+}
+
+fn option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    options(args, name).pop()
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2);
+}
+
+fn read_baseline(path: &str) -> Result<Baseline, String> {
+    let json = std::fs::read_to_string(path).map_err(|err| format!("Can't read the baseline {}: {}", path, err))?;
+    baseline_from_json(&json).map_err(|err| format!("Can't use the baseline {}: {}", path, err))
+}
+
+// Runs every day, or only the ones given by number on the command line.
+//
+// With record <file>, the times are also saved as a baseline, over the times
+// already in it for the same days. With compare <file>, the times are checked
+// against that baseline and the run fails if any step got more than
+// --slower percent slower (20 by default) or takes longer than its budget,
+// or if a day fails or one of the baseline's days isn't run.
+// --budget sets the budget of every step in milliseconds (100 by default),
+// and --budget day.step=ms, as in --budget 14.part2=60, that of a single
+// step. Each day is run --runs times (3 by default) and the fastest time of
+// each step counts.
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let mode = match args.first().map(String::as_str) {
+        Some("record" | "compare") => {
+            let mode = args.remove(0);
+            assert!(!args.is_empty(), "{} needs a baseline file", mode);
+            Some((mode, args.remove(0)))
+        },
+        _ => None,
+    };
+    let runs: usize = option(&mut args, "--runs").map_or(3, |runs| runs.parse().expect("--runs is a count"));
+    let slower: f64 = option(&mut args, "--slower").map_or(20.0, |slower| slower.parse().expect("--slower is a percentage"));
+    let mut budget = Budget::new(Duration::from_millis(100));
+    for value in options(&mut args, "--budget") {
+        match value.parse() {
+            Ok(millis) => budget.default = Duration::from_millis(millis),
+            Err(_) => budget.set(&value).unwrap_or_else(|err| fail(err)),
+        }
+    }
+    let wanted: Vec<u8> = args.iter().map(|arg| arg.parse().expect("Days are numbers")).collect();
+    // Read up front, so that a bad baseline doesn't wait for all the days. A
+    // new baseline is started if there isn't one to record over yet.
+    let mut baseline = match &mode {
+        Some((mode, path)) if mode == "record" && !std::path::Path::new(path).exists() => Baseline::new(),
+        Some((_, path)) => read_baseline(path).unwrap_or_else(|err| fail(err)),
+        None => Baseline::new(),
+    };
+
+    println!(
+        "{:>3}  {:>12}  {:>20}  {:>12}  {:<10}  {:>20}  {:>12}  Check",
+        "Day", "Parse", "Part 1", "Time", "Check", "Part 2", "Time"
+    );
+    let mut total = Duration::ZERO;
+    let mut current = Results::new();
+    for day in DAYS.iter().filter(|day| wanted.is_empty() || wanted.contains(&day.number)) {
+        let input = match day.input() {
+            Ok(input) => input,
+            Err(err) => {
+                println!("{:>3}  No input: {}", day.number, err);
+                continue;
+            },
+        };
+        match measure(day, &input, runs) {
+            Ok((run, times)) => {
+                total += times.iter().sum::<Duration>();
+                current.insert(day.number, Ok(times));
+                let check1 = run.part1.0.check(day.expected(1).as_ref());
+                let check2 = run.part2.0.check(day.expected(2).as_ref());
+                println!(
+                    "{:>3}  {:>12}  {:>20}  {:>12}  {:<10}  {:>20}  {:>12}  {}",
+                    day.number,
+                    millis(times[0]),
+                    run.part1.0,
+                    millis(times[1]),
+                    check1,
+                    run.part2.0,
+                    millis(times[2]),
+                    check2
+                );
+            },
+            Err(failure) => {
+                println!("{:>3}  {}", day.number, failure);
+                current.insert(day.number, Err(failure));
+            },
+        }
+    }
+    println!("Total: {}", millis(total));
+
+    match mode {
+        Some((mode, path)) if mode == "record" => {
+            baseline.extend(current.into_iter().filter_map(|(day, times)| Some((day, times.ok()?))));
+            if let Err(err) = std::fs::write(&path, baseline_to_json(&baseline) + "\n") {
+                fail(format!("Can't write the baseline {}: {}", path, err));
+            }
+            println!("Recorded {} days in {}", baseline.len(), path);
+        },
+        Some((_, path)) => {
+            // Only the days asked for are expected to have run
+            baseline.retain(|day, _| wanted.is_empty() || wanted.contains(day));
+            let regressions = compare(&baseline, &current, slower, &budget);
+            if regressions.is_empty() {
+                println!("No regressions against {}", path);
+                return;
+            }
+            for regression in regressions.iter() {
+                println!("{}", regression);
+            }
+            std::process::exit(1);
+        },
+        None => {},
+    }
+}